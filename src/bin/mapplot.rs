@@ -0,0 +1,428 @@
+//! The `mapplot` command-line tool, plotting GeoJSON, GPX, KML and CSV files on a Google map or as an SVG image.
+
+#![deny(unsafe_code)]
+#![warn(rust_2018_idioms)]
+#![warn(clippy::pedantic)]
+
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use mapplot::csv::{self, CsvLoader};
+use mapplot::geometry::Geometry;
+use mapplot::google::style::{Color, PolygonStyle, PolylineStyle};
+use mapplot::google::{GoogleMap, MapType, Polygon, Polyline};
+use mapplot::gpx::Gpx;
+use mapplot::kml::Kml;
+use mapplot::svg::SvgMap;
+
+const USAGE: &str = "\
+Plot GeoJSON, GPX, KML and CSV files on a Google map or as an SVG image.
+
+Usage: mapplot [OPTIONS] <FILE>...
+
+Arguments:
+  <FILE>...  Input files; the format is detected from the extension:
+             .geojson or .json, .gpx, .kml, .csv or .tsv
+
+Options:
+  -o, --output <FILE>          Output file, `-` for stdout [default: map.html, or map.svg for svg]
+  -b, --backend <BACKEND>      google (an HTML page) or svg (an image without a base map); leaflet
+                               isn't supported yet [default: svg if the output ends in .svg, else google]
+  -k, --api-key <KEY>          Google Maps API key [env: GOOGLE_MAPS_API_KEY]
+  -t, --title <TITLE>          Title of the page or image
+  -m, --map-type <TYPE>        roadmap, satellite, hybrid or terrain, google only
+      --size <WIDTHxHEIGHT>    Size of the image in pixels, svg only [default: 1024x768]
+      --center <LAT,LON>       Center of the map [default: fit all shapes]
+      --zoom <ZOOM>            Zoom level, used with --center [default: 10]
+      --color <COLOR>          Color of lines and areas, any CSS color
+      --width <PIXELS>         Width of lines
+      --fill-opacity <VALUE>   Opacity of areas, between 0 and 1
+      --csv-lat <COLUMN>       CSV column of the latitudes [default: lat or latitude]
+      --csv-lon <COLUMN>       CSV column of the longitudes [default: lon, lng or longitude]
+      --csv-title <COLUMN>     CSV column of the marker titles [default: name or title]
+      --csv-delimiter <CHAR>   CSV delimiter [default: `,`, or tab for .tsv]
+  -h, --help                   Print help
+  -V, --version                Print version
+";
+
+/// The options taking a value, i.e. all options except `--help` and `--version`.
+const OPTIONS: &[&str] = &[
+	"-o",
+	"--output",
+	"-b",
+	"--backend",
+	"-k",
+	"--api-key",
+	"-t",
+	"--title",
+	"-m",
+	"--map-type",
+	"--size",
+	"--center",
+	"--zoom",
+	"--color",
+	"--width",
+	"--fill-opacity",
+	"--csv-lat",
+	"--csv-lon",
+	"--csv-title",
+	"--csv-delimiter",
+];
+
+#[derive(Debug, Default)]
+struct Options {
+	inputs: Vec<PathBuf>,
+	output: Option<PathBuf>,
+	backend: Option<Backend>,
+	api_key: Option<String>,
+	title: Option<String>,
+	map_type: Option<MapType>,
+	size: Option<(u32, u32)>,
+	center: Option<(f64, f64)>,
+	zoom: Option<u8>,
+	style: Style,
+	csv_lat: Option<String>,
+	csv_lon: Option<String>,
+	csv_title: Option<String>,
+	csv_delimiter: Option<char>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Backend {
+	Google,
+	Svg,
+}
+
+/// The style applied to the lines and areas of the input files.
+#[derive(Debug, Default)]
+struct Style {
+	color: Option<Color>,
+	width: Option<usize>,
+	fill_opacity: Option<f32>,
+}
+
+fn main() -> ExitCode {
+	let options = match parse_args(env::args().skip(1)) {
+		Ok(Some(options)) => options,
+		Ok(None) => return ExitCode::SUCCESS,
+		Err(e) => {
+			eprintln!("mapplot: {e}\n\nFor more information, try `mapplot --help`.");
+			return ExitCode::from(2);
+		}
+	};
+
+	match run(&options) {
+		Ok(()) => ExitCode::SUCCESS,
+		Err(e) => {
+			eprintln!("mapplot: {e}");
+			ExitCode::FAILURE
+		}
+	}
+}
+
+/// Parse the command-line arguments, `None` if only the help or version was requested.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+	let mut options = Options::default();
+	while let Some(arg) = args.next() {
+		if arg == "-h" || arg == "--help" {
+			print!("{USAGE}");
+			return Ok(None);
+		}
+		if arg == "-V" || arg == "--version" {
+			println!("mapplot {}", env!("CARGO_PKG_VERSION"));
+			return Ok(None);
+		}
+		if arg == "--" {
+			options.inputs.extend(args.by_ref().map(PathBuf::from));
+			break;
+		}
+		if !arg.starts_with('-') || arg == "-" {
+			options.inputs.push(PathBuf::from(arg));
+			continue;
+		}
+
+		// `--name=value` or `--name value`
+		let (name, inline) = match arg.split_once('=') {
+			Some((name, value)) if name.starts_with("--") => {
+				(name.to_string(), Some(value.to_string()))
+			}
+			_ => (arg, None),
+		};
+		if !OPTIONS.contains(&name.as_str()) {
+			return Err(format!("unknown option `{name}`"));
+		}
+		let value = inline
+			.or_else(|| args.next())
+			.ok_or_else(|| format!("missing value for `{name}`"))?;
+		let invalid = |what: &str| format!("invalid {what} for `{name}`: {value:?}");
+
+		match name.as_str() {
+			"-o" | "--output" => options.output = Some(PathBuf::from(&value)),
+			"-b" | "--backend" => {
+				options.backend = Some(match value.to_ascii_lowercase().as_str() {
+					"google" => Backend::Google,
+					"svg" => Backend::Svg,
+					"leaflet" => {
+						return Err("the leaflet backend isn't supported yet, use google or svg"
+							.to_string())
+					}
+					_ => return Err(invalid("backend")),
+				});
+			}
+			"-k" | "--api-key" => options.api_key = Some(value),
+			"-t" | "--title" => options.title = Some(value),
+			"-m" | "--map-type" => {
+				options.map_type = Some(match value.to_ascii_lowercase().as_str() {
+					"roadmap" => MapType::Roadmap,
+					"satellite" => MapType::Satellite,
+					"hybrid" => MapType::Hybrid,
+					"terrain" => MapType::Terrain,
+					_ => return Err(invalid("map type")),
+				});
+			}
+			"--size" => {
+				options.size = Some(parse_pair(&value, 'x').ok_or_else(|| invalid("size"))?);
+			}
+			"--center" => {
+				options.center = Some(parse_pair(&value, ',').ok_or_else(|| invalid("location"))?);
+			}
+			"--zoom" => options.zoom = Some(value.parse().map_err(|_| invalid("zoom"))?),
+			"--color" => {
+				options.style.color = Some(value.parse().map_err(|e| format!("{e} for `{name}`"))?);
+			}
+			"--width" => options.style.width = Some(value.parse().map_err(|_| invalid("width"))?),
+			"--fill-opacity" => {
+				options.style.fill_opacity = Some(
+					value
+						.parse()
+						.ok()
+						.filter(|o| (0.0..=1.0).contains(o))
+						.ok_or_else(|| invalid("opacity"))?,
+				);
+			}
+			"--csv-lat" => options.csv_lat = Some(value),
+			"--csv-lon" => options.csv_lon = Some(value),
+			"--csv-title" => options.csv_title = Some(value),
+			"--csv-delimiter" => {
+				let mut chars = value.chars();
+				options.csv_delimiter = match (chars.next(), chars.next()) {
+					(Some(c), None) => Some(c),
+					_ if value == "\\t" => Some('\t'),
+					_ => return Err(invalid("delimiter")),
+				};
+			}
+			_ => unreachable!("all options are listed in `OPTIONS`"),
+		}
+	}
+
+	if options.inputs.is_empty() {
+		return Err("no input files given".to_string());
+	}
+	Ok(Some(options))
+}
+
+/// Parse two values separated by `separator`, e.g. `51.5,-0.1`.
+fn parse_pair<T: FromStr>(value: &str, separator: char) -> Option<(T, T)> {
+	let (a, b) = value.split_once(separator)?;
+	Some((a.trim().parse().ok()?, b.trim().parse().ok()?))
+}
+
+fn run(options: &Options) -> Result<(), String> {
+	let mut shapes = Vec::new();
+	for path in &options.inputs {
+		shapes.extend(load(path, options).map_err(|e| format!("{}: {e}", path.display()))?);
+	}
+
+	let backend = options.backend.unwrap_or_else(|| {
+		let extension = options.output.as_deref().and_then(Path::extension);
+		if extension.is_some_and(|e| e.eq_ignore_ascii_case("svg")) {
+			Backend::Svg
+		} else {
+			Backend::Google
+		}
+	});
+	let (output, default_path) = match backend {
+		Backend::Google => (google_map(options, shapes).to_string(), "map.html"),
+		Backend::Svg => (svg_map(options, shapes).to_string(), "map.svg"),
+	};
+
+	match options.output.as_deref() {
+		Some(path) if path == Path::new("-") => io::stdout()
+			.write_all(output.as_bytes())
+			.map_err(|e| format!("failed to write the map: {e}")),
+		path => {
+			let path = path.unwrap_or(Path::new(default_path));
+			fs::write(path, output).map_err(|e| format!("{}: {e}", path.display()))
+		}
+	}
+}
+
+fn google_map(options: &Options, shapes: Vec<Geometry>) -> GoogleMap {
+	let api_key = options
+		.api_key
+		.clone()
+		.or_else(|| env::var("GOOGLE_MAPS_API_KEY").ok());
+	if api_key.is_none() {
+		eprintln!("mapplot: warning: no API key given, the map will be shown in development mode");
+	}
+
+	let mut map = GoogleMap::new(
+		options.center.unwrap_or((0.0, 0.0)),
+		options
+			.zoom
+			.unwrap_or(if options.center.is_some() { 10 } else { 2 }),
+		api_key.as_deref(),
+	);
+	map.fit_shapes(options.center.is_none());
+	if let Some(title) = &options.title {
+		map.page_title(title);
+	}
+	if let Some(map_type) = options.map_type {
+		map.map_type(map_type);
+	}
+	if options.size.is_some() {
+		eprintln!("mapplot: warning: --size is ignored by the google backend");
+	}
+	map.draw_all(shapes);
+	map
+}
+
+fn svg_map(options: &Options, shapes: Vec<Geometry>) -> SvgMap {
+	let (width, height) = options.size.unwrap_or((1024, 768));
+	let mut map = SvgMap::new(width, height);
+	if let Some(center) = options.center {
+		map.center(center, options.zoom.unwrap_or(10));
+	}
+	if let Some(title) = &options.title {
+		map.title(title);
+	}
+	if options.map_type.is_some() || options.api_key.is_some() {
+		eprintln!("mapplot: warning: --map-type and --api-key are ignored by the svg backend");
+	}
+	map.draw_all(shapes);
+	map
+}
+
+/// Read the shapes of an input file, depending on its extension.
+fn load(path: &Path, options: &Options) -> Result<Vec<Geometry>, String> {
+	let extension = path
+		.extension()
+		.and_then(|e| e.to_str())
+		.map(str::to_ascii_lowercase);
+	let style = &options.style;
+
+	match extension.as_deref() {
+		Some("geojson" | "json") => {
+			let geojson = fs::read_to_string(path).map_err(|e| e.to_string())?;
+			let geometry = Geometry::from_geojson(&geojson).map_err(|e| e.to_string())?;
+			Ok(vec![style.geometry(geometry)])
+		}
+		Some("gpx") => {
+			let gpx = Gpx::read(path).map_err(|e| e.to_string())?;
+			Ok(gpx
+				.polylines()
+				.map(|p| Geometry::LineString(style.polyline(p)))
+				.chain(gpx.markers().map(Geometry::Point))
+				.collect())
+		}
+		Some("kml") => {
+			let kml = Kml::read(path).map_err(|e| e.to_string())?;
+			Ok(kml
+				.placemarks()
+				.iter()
+				.map(|p| style.geometry(p.geometry().clone()))
+				.collect())
+		}
+		Some(extension @ ("csv" | "tsv")) => {
+			let data = fs::read_to_string(path).map_err(|e| e.to_string())?;
+			let delimiter =
+				options
+					.csv_delimiter
+					.unwrap_or(if extension == "tsv" { '\t' } else { ',' });
+			load_csv(&data, delimiter, options)
+		}
+		_ => Err(
+			"unknown file format, expected .geojson, .json, .gpx, .kml, .csv or .tsv".to_string(),
+		),
+	}
+}
+
+/// Read the points of a CSV file as markers, detecting the columns by their names unless given.
+fn load_csv(data: &str, delimiter: char, options: &Options) -> Result<Vec<Geometry>, String> {
+	let header = csv::headers(data, delimiter).map_err(|e| e.to_string())?;
+	let column = |given: &Option<String>, candidates: &[&str]| {
+		given.clone().or_else(|| {
+			header
+				.iter()
+				.map(|h| h.trim())
+				.find(|h| candidates.contains(&h.to_ascii_lowercase().as_str()))
+				.map(ToString::to_string)
+		})
+	};
+
+	let lat = column(&options.csv_lat, &["lat", "latitude"])
+		.ok_or("no latitude column found, use --csv-lat to select it")?;
+	let lon = column(&options.csv_lon, &["lon", "lng", "long", "longitude"])
+		.ok_or("no longitude column found, use --csv-lon to select it")?;
+	let mut loader = CsvLoader::new(lat, lon).delimiter(delimiter);
+	if let Some(title) = column(&options.csv_title, &["name", "title"]) {
+		loader = loader.title(title);
+	}
+
+	let points = loader.load_str(data).map_err(|e| e.to_string())?;
+	Ok(points.iter().map(|p| Geometry::Point(p.marker())).collect())
+}
+
+impl Style {
+	fn polyline(&self, polyline: Polyline) -> Polyline {
+		if self.color.is_none() && self.width.is_none() {
+			return polyline;
+		}
+		let mut style = PolylineStyle::new();
+		if let Some(color) = self.color {
+			style = style.color(color);
+		}
+		if let Some(width) = self.width {
+			style = style.width(width);
+		}
+		polyline.style(style)
+	}
+
+	fn polygon(&self, polygon: Polygon) -> Polygon {
+		if self.color.is_none() && self.width.is_none() && self.fill_opacity.is_none() {
+			return polygon;
+		}
+		let mut style = PolygonStyle::new();
+		if let Some(color) = self.color {
+			style = style.color(color);
+		}
+		if let Some(width) = self.width {
+			style = style.stroke_width(width);
+		}
+		if let Some(opacity) = self.fill_opacity {
+			style = style.fill_opacity(opacity);
+		}
+		polygon.style(style)
+	}
+
+	fn geometry(&self, geometry: Geometry) -> Geometry {
+		match geometry {
+			Geometry::LineString(p) => Geometry::LineString(self.polyline(p)),
+			Geometry::Polygon(p) => Geometry::Polygon(self.polygon(p)),
+			Geometry::MultiLineString(p) => {
+				Geometry::MultiLineString(p.into_iter().map(|p| self.polyline(p)).collect())
+			}
+			Geometry::MultiPolygon(p) => {
+				Geometry::MultiPolygon(p.into_iter().map(|p| self.polygon(p)).collect())
+			}
+			Geometry::GeometryCollection(g) => {
+				Geometry::GeometryCollection(g.into_iter().map(|g| self.geometry(g)).collect())
+			}
+			points => points,
+		}
+	}
+}
@@ -0,0 +1,442 @@
+//! Loading of points from CSV files.
+//!
+//! # Examples
+//! ```
+//! use mapplot::csv::CsvLoader;
+//! use mapplot::google::GoogleMap;
+//!
+//! let data = "\
+//! city;latitude;longitude;kind
+//! London;51,507;-0,127;capital
+//! Birmingham;52,48;-1,902;city
+//! ";
+//!
+//! let points = CsvLoader::new("latitude", "longitude")
+//!     .delimiter(';')
+//!     .decimal_comma(true)
+//!     .title("city")
+//!     .category("kind")
+//!     .load_str(data)
+//!     .unwrap();
+//!
+//! assert_eq!(points[0].category(), Some("capital"));
+//!
+//! let html = GoogleMap::new((52.0, -1.0), 7, "<your-apikey-here>")
+//!     .draw_all(points.iter().map(|p| p.marker()))
+//!     .to_string();
+//!
+//! std::fs::write("map.html", html).unwrap();
+//! ```
+//!
+//! Malformed rows are reported together with their line number:
+//! ```
+//! use mapplot::csv::CsvLoader;
+//!
+//! let err = CsvLoader::new(0, 1).has_headers(false).load_str("1,2\n3,x\n").unwrap_err();
+//! assert_eq!(err.to_string(), "malformed CSV row on line 2: invalid longitude: \"x\"");
+//!
+//! let err = CsvLoader::new(0, 1).has_headers(false).load_str("1,2\nNaN,3\n95,3\n").unwrap_err();
+//! assert_eq!(err.to_string(), "malformed CSV row on line 2: invalid latitude: \"NaN\"");
+//!
+//! let err = CsvLoader::new(0, 1).has_headers(false).load_str("1,2\n89,3\n3,-180.5\n").unwrap_err();
+//! assert_eq!(err.to_string(), "malformed CSV row on line 3: longitude out of range: \"-180.5\"");
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::str::Chars;
+use std::{fs, io};
+
+use crate::google::Marker;
+use crate::Location;
+
+/// A column of a CSV file, either referenced by its header name or by its zero-based index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Column {
+	Name(String),
+	Index(usize),
+}
+
+impl From<&str> for Column {
+	fn from(name: &str) -> Self {
+		Column::Name(name.to_string())
+	}
+}
+
+impl From<String> for Column {
+	fn from(name: String) -> Self {
+		Column::Name(name)
+	}
+}
+
+impl From<usize> for Column {
+	fn from(index: usize) -> Self {
+		Column::Index(index)
+	}
+}
+
+/// Maps the columns of a CSV file to points.
+#[derive(Debug, Clone)]
+pub struct CsvLoader {
+	latitude: Column,
+	longitude: Column,
+	label: Option<Column>,
+	title: Option<Column>,
+	category: Option<Column>,
+	delimiter: char,
+	decimal_comma: bool,
+	has_headers: bool,
+}
+
+impl CsvLoader {
+	/// Create a new loader reading the coordinates from the given columns.
+	#[must_use]
+	pub fn new(latitude: impl Into<Column>, longitude: impl Into<Column>) -> Self {
+		CsvLoader {
+			latitude: latitude.into(),
+			longitude: longitude.into(),
+			label: None,
+			title: None,
+			category: None,
+			delimiter: ',',
+			decimal_comma: false,
+			has_headers: true,
+		}
+	}
+
+	/// The column used as the [`Marker::label`].
+	#[must_use]
+	pub fn label(mut self, column: impl Into<Column>) -> Self {
+		self.label = Some(column.into());
+		self
+	}
+
+	/// The column used as the [`Marker::title`].
+	#[must_use]
+	pub fn title(mut self, column: impl Into<Column>) -> Self {
+		self.title = Some(column.into());
+		self
+	}
+
+	/// The column used to group points into categories.
+	#[must_use]
+	pub fn category(mut self, column: impl Into<Column>) -> Self {
+		self.category = Some(column.into());
+		self
+	}
+
+	/// The field delimiter. Defaults to `,`.
+	#[must_use]
+	pub fn delimiter(mut self, value: char) -> Self {
+		self.delimiter = value;
+		self
+	}
+
+	/// Whether the coordinates use a comma as the decimal separator, e.g. `51,507`. Defaults to `false`.
+	#[must_use]
+	pub fn decimal_comma(mut self, value: bool) -> Self {
+		self.decimal_comma = value;
+		self
+	}
+
+	/// Whether the first row contains the column names. Columns can only be referenced by name if it does. Defaults to `true`.
+	#[must_use]
+	pub fn has_headers(mut self, value: bool) -> Self {
+		self.has_headers = value;
+		self
+	}
+
+	/// Read and load a CSV file.
+	///
+	/// # Errors
+	/// Returns an error if the file can't be read, a configured column doesn't exist or a row is malformed.
+	pub fn load(&self, path: impl AsRef<Path>) -> Result<Vec<CsvPoint>, Error> {
+		self.load_str(&fs::read_to_string(path)?)
+	}
+
+	/// Load CSV data from a string. A leading byte order mark, as written by Excel, is ignored.
+	///
+	/// # Errors
+	/// Returns an error if a configured column doesn't exist or a row is malformed, including coordinates which aren't
+	/// finite or lie outside of ±90° latitude and ±180° longitude.
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::csv::CsvLoader;
+	///
+	/// let points = CsvLoader::new("lat", "lon").load_str("\u{feff}lat,lon\n51.507,-0.127\n").unwrap();
+	/// assert_eq!(points.len(), 1);
+	/// ```
+	pub fn load_str(&self, data: &str) -> Result<Vec<CsvPoint>, Error> {
+		let mut rows = Rows::new(strip_bom(data), self.delimiter);
+
+		let headers = if self.has_headers {
+			match rows.next() {
+				Some(row) => row?.1,
+				None => return Ok(Vec::new()),
+			}
+		} else {
+			Vec::new()
+		};
+
+		let index = |column: &Column| match column {
+			Column::Index(i) => Ok(*i),
+			Column::Name(name) => headers
+				.iter()
+				.position(|h| h.trim() == name)
+				.ok_or_else(|| Error::MissingColumn(name.clone())),
+		};
+		let index_opt = |column: &Option<Column>| column.as_ref().map(index).transpose();
+
+		let latitude = index(&self.latitude)?;
+		let longitude = index(&self.longitude)?;
+		let label = index_opt(&self.label)?;
+		let title = index_opt(&self.title)?;
+		let category = index_opt(&self.category)?;
+
+		let mut points = Vec::new();
+		for row in rows {
+			let (line, fields) = row?;
+			if fields.iter().all(|f| f.trim().is_empty()) {
+				continue;
+			}
+
+			let field = |i: usize| {
+				fields.get(i).map(String::as_str).ok_or_else(|| Error::Row {
+					line,
+					message: format!("expected at least {} fields, found {}", i + 1, fields.len()),
+				})
+			};
+			let coord = |i: usize, name: &str, limit: f64| {
+				let value = field(i)?.trim();
+				let parsed = if self.decimal_comma {
+					value.replace(',', ".").parse::<f64>()
+				} else {
+					value.parse::<f64>()
+				};
+				let message = match parsed {
+					Ok(coord) if coord.abs() <= limit => return Ok(coord),
+					Ok(coord) if coord.is_finite() => format!("{name} out of range: {value:?}"),
+					_ => format!("invalid {name}: {value:?}"),
+				};
+				Err(Error::Row { line, message })
+			};
+			let text = |i: Option<usize>| i.map(|i| field(i).map(ToString::to_string)).transpose();
+
+			points.push(CsvPoint {
+				location: Location::new(
+					coord(latitude, "latitude", 90.0)?,
+					coord(longitude, "longitude", 180.0)?,
+				),
+				label: text(label)?,
+				title: text(title)?,
+				category: text(category)?,
+				line,
+			});
+		}
+
+		Ok(points)
+	}
+}
+
+/// A single point loaded from a CSV row.
+#[derive(Debug, Clone)]
+pub struct CsvPoint {
+	location: Location,
+	label: Option<String>,
+	title: Option<String>,
+	category: Option<String>,
+	line: usize,
+}
+
+impl CsvPoint {
+	/// The position of the point.
+	#[must_use]
+	pub fn location(&self) -> Location {
+		self.location
+	}
+
+	/// The value of the label column.
+	#[must_use]
+	pub fn label(&self) -> Option<&str> {
+		self.label.as_deref()
+	}
+
+	/// The value of the title column.
+	#[must_use]
+	pub fn title(&self) -> Option<&str> {
+		self.title.as_deref()
+	}
+
+	/// The value of the category column.
+	#[must_use]
+	pub fn category(&self) -> Option<&str> {
+		self.category.as_deref()
+	}
+
+	/// The line of the CSV file on which the row starts, starting at 1.
+	#[must_use]
+	pub fn line(&self) -> usize {
+		self.line
+	}
+
+	/// A marker with the label and title of this point.
+	#[must_use]
+	pub fn marker(&self) -> Marker {
+		let mut marker = Marker::new(self.location);
+		if let Some(label) = &self.label {
+			marker = marker.label(label);
+		}
+		if let Some(title) = &self.title {
+			marker = marker.title(title);
+		}
+		marker
+	}
+}
+
+impl From<&CsvPoint> for Location {
+	fn from(p: &CsvPoint) -> Self {
+		p.location
+	}
+}
+
+impl From<&CsvPoint> for Marker {
+	fn from(p: &CsvPoint) -> Self {
+		p.marker()
+	}
+}
+
+/// The names in the header row of CSV data, e.g. for picking the columns of a [`CsvLoader`]. Like when loading the
+/// data, quoted names are unquoted and a leading byte order mark is ignored.
+///
+/// # Errors
+/// Returns an error if the header row has an unterminated quoted field.
+///
+/// # Examples
+/// ```
+/// let headers = mapplot::csv::headers("\u{feff}city;\"lat; deg\";lon\nLondon;51.507;-0.127\n", ';').unwrap();
+/// assert_eq!(headers, ["city", "lat; deg", "lon"]);
+/// ```
+pub fn headers(data: &str, delimiter: char) -> Result<Vec<String>, Error> {
+	match Rows::new(strip_bom(data), delimiter).next() {
+		Some(row) => Ok(row?.1),
+		None => Ok(Vec::new()),
+	}
+}
+
+fn strip_bom(data: &str) -> &str {
+	data.strip_prefix('\u{feff}').unwrap_or(data)
+}
+
+/// Splits CSV data into rows of fields, honoring quoted fields.
+struct Rows<'a> {
+	chars: Chars<'a>,
+	delimiter: char,
+	line: usize,
+}
+
+impl<'a> Rows<'a> {
+	fn new(data: &'a str, delimiter: char) -> Self {
+		Rows {
+			chars: data.chars(),
+			delimiter,
+			line: 1,
+		}
+	}
+}
+
+impl Iterator for Rows<'_> {
+	type Item = Result<(usize, Vec<String>), Error>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let start = self.line;
+		let mut fields = Vec::new();
+		let mut field = String::new();
+		let mut quoted = false;
+		let mut empty = true;
+
+		loop {
+			let Some(c) = self.chars.next() else {
+				if quoted {
+					return Some(Err(Error::Row {
+						line: start,
+						message: "unterminated quoted field".to_string(),
+					}));
+				}
+				if empty {
+					return None;
+				}
+				break;
+			};
+			empty = false;
+
+			match c {
+				'"' if quoted => {
+					if self.chars.clone().next() == Some('"') {
+						self.chars.next();
+						field.push('"');
+					} else {
+						quoted = false;
+					}
+				}
+				'"' if field.trim().is_empty() => {
+					field.clear();
+					quoted = true;
+				}
+				'\n' => {
+					self.line += 1;
+					if quoted {
+						field.push(c);
+					} else {
+						break;
+					}
+				}
+				'\r' if !quoted => {}
+				c if c == self.delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+				c => field.push(c),
+			}
+		}
+
+		fields.push(field);
+		Some(Ok((start, fields)))
+	}
+}
+
+/// An error that occurred while loading a CSV file.
+#[derive(Debug)]
+pub enum Error {
+	/// The file couldn't be read.
+	Io(io::Error),
+	/// A column referenced by name isn't present in the header row.
+	MissingColumn(String),
+	/// A row is malformed.
+	Row { line: usize, message: String },
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Io(e) => write!(f, "failed to read CSV file: {e}"),
+			Error::MissingColumn(name) => write!(f, "column {name:?} not found in the CSV header"),
+			Error::Row { line, message } => {
+				write!(f, "malformed CSV row on line {line}: {message}")
+			}
+		}
+	}
+}
+
+impl StdError for Error {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Error::Io(e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
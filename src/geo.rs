@@ -0,0 +1,228 @@
+//! Conversions from and to the [`geo_types`] crate.
+//!
+//! The module has no items of its own, the conversions are [`From`] implementations:
+//! - [`Coord`] and [`Point`] to and from [`Location`], [`Point`] to [`Marker`]
+//! - [`Rect`] to and from [`BoundingBox`], to and from [`Rectangle`] and from `&Rectangle`
+//! - [`LineString`] to and from [`Polyline`] and from `&Polyline`
+//! - [`geo_types::Polygon`] to and from [`Polygon`] and from `&Polygon`, and thereby [`MultiPolygon`] from [`Polygon`]
+//!   and `&Polygon`
+//! - [`MultiPolygon`], [`geo_types::Polygon`], [`LineString`] and [`Point`] to [`Geometry`], and `&Geometry` to
+//!   [`geo_types::Geometry`]
+//!
+//! # Examples
+//! ```
+//! use geo_types::{polygon, Rect};
+//! use mapplot::google::{GoogleMap, Polygon, Rectangle};
+//!
+//! let zone = polygon!(
+//!     exterior: [(x: 7.0, y: 46.0), (x: 9.0, y: 46.0), (x: 8.0, y: 47.5)],
+//!     interiors: [[(x: 7.8, y: 46.4), (x: 8.2, y: 46.4), (x: 8.0, y: 46.8)]],
+//! );
+//!
+//! let html = GoogleMap::new((46.5, 8.0), 7, "<your-apikey-here>")
+//!     .draw(Polygon::from(zone))
+//!     .draw(Rectangle::from(Rect::new((6.0, 45.0), (10.0, 48.0))))
+//!     .to_string();
+//!
+//! std::fs::write("map.html", html).unwrap();
+//! ```
+//!
+//! The shapes convert back, e.g. for geometric operations of the [`geo`](https://docs.rs/geo) crate:
+//! ```
+//! use geo_types::{LineString, MultiPolygon};
+//! use mapplot::google::{Polygon, Polyline};
+//!
+//! let route = LineString::from(Polyline::new([(51.507, -0.127), (52.48, -1.902)]));
+//! assert_eq!(route.0.len(), 2);
+//!
+//! let zones = MultiPolygon::from(Polygon::new([(46.0, 7.0), (46.0, 9.0), (47.5, 8.0)]));
+//! // geo_types closes the ring
+//! assert_eq!(zones.0[0].exterior().0.len(), 4);
+//! ```
+
+use geo_types::{Coord, LineString, MultiPolygon, Point, Rect};
+
+use crate::geometry::Geometry;
+use crate::google::{Marker, Polygon, Polyline, Rectangle};
+use crate::{BoundingBox, Location};
+
+impl From<Coord<f64>> for Location {
+	fn from(c: Coord<f64>) -> Self {
+		Location::new(c.y, c.x)
+	}
+}
+
+impl From<Location> for Coord<f64> {
+	fn from(l: Location) -> Self {
+		Coord { x: l.lon, y: l.lat }
+	}
+}
+
+impl From<Point<f64>> for Location {
+	fn from(p: Point<f64>) -> Self {
+		p.0.into()
+	}
+}
+
+impl From<Location> for Point<f64> {
+	fn from(l: Location) -> Self {
+		Point(l.into())
+	}
+}
+
+impl From<Point<f64>> for Marker {
+	fn from(p: Point<f64>) -> Self {
+		Marker::new(p)
+	}
+}
+
+impl From<Rect<f64>> for BoundingBox {
+	fn from(r: Rect<f64>) -> Self {
+		BoundingBox::new(r.min().into(), r.max().into())
+	}
+}
+
+impl From<BoundingBox> for Rect<f64> {
+	fn from(b: BoundingBox) -> Self {
+		Rect::new(b.p1, b.p2)
+	}
+}
+
+impl From<Rect<f64>> for Rectangle {
+	fn from(r: Rect<f64>) -> Self {
+		Rectangle::new(r.min(), r.max())
+	}
+}
+
+impl From<&Rectangle> for Rect<f64> {
+	fn from(r: &Rectangle) -> Self {
+		r.bounds.into()
+	}
+}
+
+impl From<Rectangle> for Rect<f64> {
+	fn from(r: Rectangle) -> Self {
+		(&r).into()
+	}
+}
+
+impl From<LineString<f64>> for Polyline {
+	fn from(l: LineString<f64>) -> Self {
+		Polyline::new(l)
+	}
+}
+
+impl From<&Polyline> for LineString<f64> {
+	fn from(p: &Polyline) -> Self {
+		p.path.iter().copied().collect()
+	}
+}
+
+impl From<Polyline> for LineString<f64> {
+	fn from(p: Polyline) -> Self {
+		(&p).into()
+	}
+}
+
+/// The interior rings are rewound if necessary, since Google Maps only cuts out holes whose winding is opposite to
+/// the exterior ring.
+impl From<geo_types::Polygon<f64>> for Polygon {
+	fn from(p: geo_types::Polygon<f64>) -> Self {
+		let (exterior, interiors) = p.into_inner();
+		let exterior = open_ring(exterior);
+		let clockwise = signed_area(&exterior) < 0.0;
+
+		interiors.into_iter().map(open_ring).fold(
+			Polygon::new(exterior),
+			|polygon, mut interior| {
+				if (signed_area(&interior) < 0.0) == clockwise {
+					interior.reverse();
+				}
+				polygon.path(interior)
+			},
+		)
+	}
+}
+
+/// The first path becomes the exterior ring, all others interior rings.
+impl From<&Polygon> for geo_types::Polygon<f64> {
+	fn from(p: &Polygon) -> Self {
+		let mut rings = p
+			.paths
+			.iter()
+			.map(|path| path.iter().copied().collect::<LineString<f64>>());
+		let exterior = rings.next().unwrap_or_else(|| LineString::new(Vec::new()));
+		geo_types::Polygon::new(exterior, rings.collect())
+	}
+}
+
+impl From<Polygon> for geo_types::Polygon<f64> {
+	fn from(p: Polygon) -> Self {
+		(&p).into()
+	}
+}
+
+impl From<MultiPolygon<f64>> for Geometry {
+	fn from(p: MultiPolygon<f64>) -> Self {
+		Geometry::MultiPolygon(p.into_iter().map(Into::into).collect())
+	}
+}
+
+impl From<geo_types::Polygon<f64>> for Geometry {
+	fn from(p: geo_types::Polygon<f64>) -> Self {
+		Geometry::Polygon(p.into())
+	}
+}
+
+impl From<LineString<f64>> for Geometry {
+	fn from(l: LineString<f64>) -> Self {
+		Geometry::LineString(l.into())
+	}
+}
+
+impl From<Point<f64>> for Geometry {
+	fn from(p: Point<f64>) -> Self {
+		Geometry::Point(p.into())
+	}
+}
+
+impl From<&Geometry> for geo_types::Geometry<f64> {
+	fn from(g: &Geometry) -> Self {
+		match g {
+			Geometry::Point(m) => Point::from(m.position).into(),
+			Geometry::LineString(p) => LineString::from(p).into(),
+			Geometry::Polygon(p) => geo_types::Polygon::from(p).into(),
+			Geometry::MultiPoint(m) => m
+				.iter()
+				.map(|m| Point::from(m.position))
+				.collect::<geo_types::MultiPoint<f64>>()
+				.into(),
+			Geometry::MultiLineString(p) => {
+				geo_types::MultiLineString::new(p.iter().map(Into::into).collect()).into()
+			}
+			Geometry::MultiPolygon(p) => {
+				MultiPolygon::new(p.iter().map(Into::into).collect()).into()
+			}
+			Geometry::GeometryCollection(g) => geo_types::Geometry::GeometryCollection(
+				g.iter().map(geo_types::Geometry::from).collect(),
+			),
+		}
+	}
+}
+
+/// Drop the closing coordinate, as rings are implicitly closed on the map.
+fn open_ring(ring: LineString<f64>) -> Vec<Location> {
+	let mut coords = ring.0;
+	if coords.len() > 1 && coords.first() == coords.last() {
+		coords.pop();
+	}
+	coords.into_iter().map(Into::into).collect()
+}
+
+/// Twice the signed area of the ring, positive if wound counter-clockwise.
+fn signed_area(ring: &[Location]) -> f64 {
+	ring.iter()
+		.zip(ring.iter().cycle().skip(1))
+		.map(|(a, b)| a.lon * b.lat - b.lon * a.lat)
+		.sum()
+}
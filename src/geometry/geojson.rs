@@ -0,0 +1,119 @@
+use crate::geometry::{polygon_from_rings, Error, Geometry};
+use crate::google::{Marker, Polyline};
+use crate::json::{self, Value};
+use crate::Location;
+
+pub(super) fn parse(s: &str) -> Result<Geometry, Error> {
+	let root = json::parse(s).map_err(Error::GeoJson)?;
+	match root.get("type").and_then(Value::as_str) {
+		Some("FeatureCollection") => Ok(Geometry::GeometryCollection(
+			root.get("features")
+				.and_then(Value::as_array)
+				.ok_or_else(|| invalid("FeatureCollection without features"))?
+				.iter()
+				.map(feature)
+				.collect::<Result<_, _>>()?,
+		)),
+		Some("Feature") => feature(&root),
+		_ => geometry(&root),
+	}
+}
+
+fn invalid(msg: &str) -> Error {
+	Error::GeoJson(msg.to_string())
+}
+
+/// The geometry of a feature, with points titled by its `name` or `title` property.
+fn feature(feature: &Value) -> Result<Geometry, Error> {
+	let title = feature
+		.get("properties")
+		.and_then(|p| p.get("name").or_else(|| p.get("title")))
+		.and_then(Value::as_str);
+	let geometry = match feature.get("geometry") {
+		// an unlocated feature
+		None | Some(Value::Null) => return Ok(Geometry::GeometryCollection(Vec::new())),
+		Some(value) => geometry(value)?,
+	};
+	Ok(match (geometry, title) {
+		(Geometry::Point(marker), Some(title)) => Geometry::Point(marker.title(title)),
+		(Geometry::MultiPoint(markers), Some(title)) => {
+			Geometry::MultiPoint(markers.into_iter().map(|m| m.title(title)).collect())
+		}
+		(geometry, _) => geometry,
+	})
+}
+
+fn geometry(geometry: &Value) -> Result<Geometry, Error> {
+	let kind = geometry
+		.get("type")
+		.and_then(Value::as_str)
+		.ok_or_else(|| invalid("missing type"))?;
+	if kind == "GeometryCollection" {
+		return Ok(Geometry::GeometryCollection(
+			geometry
+				.get("geometries")
+				.and_then(Value::as_array)
+				.ok_or_else(|| invalid("GeometryCollection without geometries"))?
+				.iter()
+				.map(self::geometry)
+				.collect::<Result<_, _>>()?,
+		));
+	}
+
+	let coordinates = geometry
+		.get("coordinates")
+		.ok_or_else(|| invalid(&format!("{kind} without coordinates")))?;
+	let invalid = || invalid(&format!("invalid coordinates of {kind}"));
+
+	match kind {
+		"Point" => Ok(Geometry::Point(Marker::new(
+			position(coordinates).ok_or_else(invalid)?,
+		))),
+		"MultiPoint" => Ok(Geometry::MultiPoint(
+			positions(coordinates)
+				.ok_or_else(invalid)?
+				.into_iter()
+				.map(Marker::new)
+				.collect(),
+		)),
+		"LineString" => Ok(Geometry::LineString(Polyline::new(
+			positions(coordinates).ok_or_else(invalid)?,
+		))),
+		"MultiLineString" => Ok(Geometry::MultiLineString(
+			rings(coordinates)
+				.ok_or_else(invalid)?
+				.into_iter()
+				.map(Polyline::new)
+				.collect(),
+		)),
+		"Polygon" => Ok(Geometry::Polygon(polygon_from_rings(
+			rings(coordinates).ok_or_else(invalid)?,
+		))),
+		"MultiPolygon" => Ok(Geometry::MultiPolygon(
+			coordinates
+				.as_array()
+				.and_then(|polygons| polygons.iter().map(rings).collect::<Option<Vec<_>>>())
+				.ok_or_else(invalid)?
+				.into_iter()
+				.map(polygon_from_rings)
+				.collect(),
+		)),
+		kind => Err(self::invalid(&format!("unsupported type {kind:?}"))),
+	}
+}
+
+/// A `[longitude, latitude]` position, ignoring the altitude.
+fn position(value: &Value) -> Option<Location> {
+	match value.as_array()? {
+		[lon, lat, ..] => Some(Location::new(lat.as_f64()?, lon.as_f64()?)),
+		_ => None,
+	}
+}
+
+fn positions(value: &Value) -> Option<Vec<Location>> {
+	value.as_array()?.iter().map(position).collect()
+}
+
+fn rings(value: &Value) -> Option<Vec<Vec<Location>>> {
+	value.as_array()?.iter().map(positions).collect()
+}
@@ -0,0 +1,366 @@
+//! Conversions between the shapes of this crate and the
+//! [WKT and WKB](https://en.wikipedia.org/wiki/Well-known_text_representation_of_geometry) formats, as well as reading
+//! of [GeoJSON](https://datatracker.ietf.org/doc/html/rfc7946).
+//!
+//! Coordinates are expected in `x y` (i.e. `longitude latitude`) order. Z and M values as well as the
+//! `PostGIS`-specific `SRID=...;` prefix (EWKT) and SRID flag (EWKB) are accepted but ignored.
+//!
+//! # Examples
+//! ```
+//! use mapplot::geometry::Geometry;
+//! use mapplot::google::GoogleMap;
+//!
+//! let geometry: Geometry = "MULTIPOLYGON(((7 46, 8 47, 9 46, 7 46)), ((1 1, 2 2, 3 1, 1 1)))".parse().unwrap();
+//!
+//! let html = GoogleMap::new((46.0, 7.0), 6, "<your-apikey-here>")
+//!     .draw(geometry.clone())
+//!     .to_string();
+//!
+//! assert_eq!(geometry.to_string(), "MULTIPOLYGON(((7 46, 8 47, 9 46, 7 46)), ((1 1, 2 2, 3 1, 1 1)))");
+//! assert_eq!(Geometry::from_wkb(&geometry.to_wkb()).unwrap().to_string(), geometry.to_string());
+//!
+//! std::fs::write("map.html", html).unwrap();
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter, Write};
+use std::time::SystemTime;
+
+use crate::google::{fmt_part, Action, Event, Marker, Polygon, Polyline, Rectangle, Shape};
+use crate::{BoundingBox, Location};
+
+mod geojson;
+mod wkb;
+mod wkt;
+
+/// The deepest nesting of geometry collections accepted when parsing WKT and WKB, so malicious input can't overflow the
+/// stack.
+const MAX_DEPTH: usize = 128;
+
+/// A geometry made of one or more shapes.
+///
+/// Implements [`Shape`], so it can be drawn directly. The WKT representation is available through
+/// [`FromStr`](std::str::FromStr) and [`Display`]. Since a [`Marker`] or [`Polygon`] can't be empty, `POINT EMPTY` and
+/// `POLYGON EMPTY` are represented as an empty [`Geometry::MultiPoint`] and [`Geometry::MultiPolygon`] respectively.
+/// Empty shapes and members are written as `EMPTY`, and empty rings of polygons are left out.
+///
+/// The event listeners and IDs of the shapes are kept, including those of the members of multi-geometries and
+/// collections.
+///
+/// # Examples
+/// ```
+/// use mapplot::geometry::Geometry;
+/// use mapplot::google::{Polygon, Polyline};
+///
+/// let polygon = Geometry::Polygon(Polygon::new(Vec::<(f64, f64)>::new()));
+/// assert_eq!(polygon.to_string(), "POLYGON EMPTY");
+/// assert_eq!(polygon.to_string().parse::<Geometry>().unwrap().to_string(), "MULTIPOLYGON EMPTY");
+///
+/// let lines = Geometry::MultiLineString(vec![
+///     Polyline::new(Vec::<(f64, f64)>::new()),
+///     Polyline::new([(46.0, 7.0), (47.0, 8.0)]),
+/// ]);
+/// assert_eq!(lines.to_string(), "MULTILINESTRING(EMPTY, (7 46, 8 47))");
+/// assert_eq!(lines.to_string().parse::<Geometry>().unwrap().to_string(), lines.to_string());
+/// assert_eq!(Geometry::from_wkb(&lines.to_wkb()).unwrap().to_string(), lines.to_string());
+/// ```
+///
+/// ```
+/// use mapplot::geometry::Geometry;
+/// use mapplot::google::{Action, Event, GoogleMap, Marker};
+///
+/// let stops = Geometry::MultiPoint(vec![
+///     Marker::new((51.507, -0.127)).id("london"),
+///     Marker::new((52.48, -1.902)).on(Event::Click, Action::ZoomTo),
+/// ]);
+///
+/// let html = GoogleMap::new((52.0, -1.0), 7, "<your-apikey-here>")
+///     .draw(stops)
+///     .to_string();
+///
+/// assert!(html.contains("const __shapes = window.__shapes = {};"));
+/// assert!(html.contains(r#"[__id("london", new google.maps.Marker("#));
+/// assert!(html.contains(", __on(new google.maps.Marker("));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Geometry {
+	Point(Marker),
+	LineString(Polyline),
+	Polygon(Polygon),
+	MultiPoint(Vec<Marker>),
+	MultiLineString(Vec<Polyline>),
+	MultiPolygon(Vec<Polygon>),
+	GeometryCollection(Vec<Geometry>),
+}
+
+impl Geometry {
+	/// Parse a geometry from its WKT representation. Equivalent to [`str::parse`].
+	///
+	/// # Errors
+	/// Returns an error if `wkt` isn't valid WKT.
+	pub fn from_wkt(wkt: &str) -> Result<Self, Error> {
+		wkt::parse(wkt)
+	}
+
+	/// The WKT representation of the geometry. Equivalent to [`ToString::to_string`].
+	#[must_use]
+	pub fn to_wkt(&self) -> String {
+		self.to_string()
+	}
+
+	/// Parse a geometry from its WKB representation.
+	///
+	/// # Errors
+	/// Returns an error if `wkb` isn't valid WKB.
+	pub fn from_wkb(wkb: &[u8]) -> Result<Self, Error> {
+		wkb::parse(wkb)
+	}
+
+	/// Parse a geometry from its hex encoded WKB representation, as returned by `PostGIS`.
+	///
+	/// # Errors
+	/// Returns an error if `hex` isn't valid hex encoded WKB.
+	pub fn from_wkb_hex(hex: &str) -> Result<Self, Error> {
+		let hex = hex.trim();
+		if !hex.len().is_multiple_of(2) {
+			return Err(Error::Wkb("odd number of hex digits".to_string()));
+		}
+		let bytes = (0..hex.len())
+			.step_by(2)
+			.map(|i| {
+				hex.get(i..i + 2)
+					.and_then(|b| u8::from_str_radix(b, 16).ok())
+					.ok_or_else(|| Error::Wkb(format!("invalid hex digits at offset {i}")))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		wkb::parse(&bytes)
+	}
+
+	/// The little-endian WKB representation of the geometry.
+	///
+	/// # Panics
+	/// Panics if a shape or collection has more than [`u32::MAX`] coordinates, rings or members, which WKB can't
+	/// represent.
+	#[must_use]
+	pub fn to_wkb(&self) -> Vec<u8> {
+		let mut buf = Vec::new();
+		wkb::write(self, &mut buf);
+		buf
+	}
+
+	/// Parse a GeoJSON geometry, `Feature` or `FeatureCollection`. Features become the geometries they contain, a
+	/// `FeatureCollection` a [`Geometry::GeometryCollection`]. Points of features with a `name` or `title` property
+	/// get it as their title, all other properties are ignored.
+	///
+	/// # Errors
+	/// Returns an error if `geojson` isn't valid GeoJSON.
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::geometry::Geometry;
+	///
+	/// let geojson = r#"{
+	///     "type": "FeatureCollection",
+	///     "features": [
+	///         { "type": "Feature", "properties": { "name": "London" }, "geometry": { "type": "Point", "coordinates": [-0.127, 51.507] } },
+	///         { "type": "Feature", "properties": {}, "geometry": { "type": "LineString", "coordinates": [[-0.127, 51.507], [-1.902, 52.48]] } }
+	///     ]
+	/// }"#;
+	///
+	/// let geometry = Geometry::from_geojson(geojson).unwrap();
+	/// assert_eq!(geometry.to_string(), "GEOMETRYCOLLECTION(POINT(-0.127 51.507), LINESTRING(-0.127 51.507, -1.902 52.48))");
+	/// ```
+	pub fn from_geojson(geojson: &str) -> Result<Self, Error> {
+		geojson::parse(geojson)
+	}
+
+	/// The hex encoded little-endian WKB representation of the geometry.
+	///
+	/// # Panics
+	/// Panics under the same conditions as [`Geometry::to_wkb`].
+	#[must_use]
+	pub fn to_wkb_hex(&self) -> String {
+		self.to_wkb().iter().fold(String::new(), |mut hex, b| {
+			let _ = write!(hex, "{b:02X}");
+			hex
+		})
+	}
+}
+
+impl Shape for Geometry {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		fn fmt_all(f: &mut Formatter<'_>, shapes: &[impl Shape]) -> fmt::Result {
+			f.write_str("[")?;
+			for (i, shape) in shapes.iter().enumerate() {
+				if i > 0 {
+					f.write_str(", ")?;
+				}
+				fmt_part(f, shape)?;
+			}
+			f.write_str("]")
+		}
+
+		match self {
+			Geometry::Point(m) => m.fmt_js(f),
+			Geometry::LineString(p) => p.fmt_js(f),
+			Geometry::Polygon(p) => p.fmt_js(f),
+			Geometry::MultiPoint(m) => fmt_all(f, m),
+			Geometry::MultiLineString(p) => fmt_all(f, p),
+			Geometry::MultiPolygon(p) => fmt_all(f, p),
+			Geometry::GeometryCollection(g) => fmt_all(f, g),
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<crate::google::serde_shapes::ShapeRepr<'_>> {
+		Some(crate::google::serde_shapes::ShapeRepr::Geometry { geometry: self })
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		match self {
+			Geometry::Point(m) => m.time_range(),
+			Geometry::LineString(p) => p.time_range(),
+			Geometry::Polygon(p) => p.time_range(),
+			_ => None,
+		}
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		fn union(shapes: &[impl Shape]) -> Option<BoundingBox> {
+			shapes
+				.iter()
+				.filter_map(Shape::bounds)
+				.reduce(BoundingBox::union)
+		}
+
+		match self {
+			Geometry::Point(m) => m.bounds(),
+			Geometry::LineString(p) => p.bounds(),
+			Geometry::Polygon(p) => p.bounds(),
+			Geometry::MultiPoint(m) => union(m),
+			Geometry::MultiLineString(p) => union(p),
+			Geometry::MultiPolygon(p) => union(p),
+			Geometry::GeometryCollection(g) => union(g),
+		}
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		match self {
+			Geometry::Point(m) => m.events(),
+			Geometry::LineString(p) => p.events(),
+			Geometry::Polygon(p) => p.events(),
+			_ => &[],
+		}
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		match self {
+			Geometry::Point(m) => m.shape_id(),
+			Geometry::LineString(p) => p.shape_id(),
+			Geometry::Polygon(p) => p.shape_id(),
+			_ => None,
+		}
+	}
+
+	fn is_editable(&self) -> bool {
+		match self {
+			Geometry::Point(m) => m.is_editable(),
+			Geometry::LineString(p) => p.is_editable(),
+			Geometry::Polygon(p) => p.is_editable(),
+			_ => false,
+		}
+	}
+
+	fn parts(&self) -> Vec<&dyn Shape> {
+		fn all(shapes: &[impl Shape]) -> Vec<&dyn Shape> {
+			shapes.iter().map(|s| s as &dyn Shape).collect()
+		}
+
+		match self {
+			Geometry::Point(_) | Geometry::LineString(_) | Geometry::Polygon(_) => Vec::new(),
+			Geometry::MultiPoint(m) => all(m),
+			Geometry::MultiLineString(p) => all(p),
+			Geometry::MultiPolygon(p) => all(p),
+			// the members of nested multi-geometries and collections too
+			Geometry::GeometryCollection(g) => g
+				.iter()
+				.flat_map(|g| std::iter::once(g as &dyn Shape).chain(g.parts()))
+				.collect(),
+		}
+	}
+}
+
+impl From<Marker> for Geometry {
+	fn from(m: Marker) -> Self {
+		Geometry::Point(m)
+	}
+}
+
+impl From<Polyline> for Geometry {
+	fn from(p: Polyline) -> Self {
+		Geometry::LineString(p)
+	}
+}
+
+impl From<Polygon> for Geometry {
+	fn from(p: Polygon) -> Self {
+		Geometry::Polygon(p)
+	}
+}
+
+impl From<Rectangle> for Geometry {
+	fn from(r: Rectangle) -> Self {
+		Geometry::Polygon(r.into())
+	}
+}
+
+/// Rings of a polygon, closed as required by WKT and WKB.
+fn closed_rings(polygon: &Polygon) -> impl Iterator<Item = Vec<Location>> + '_ {
+	polygon.paths.iter().map(|path| {
+		let mut ring = path.clone();
+		if let (Some(first), Some(last)) = (path.first(), path.last()) {
+			if (first.lat, first.lon) != (last.lat, last.lon) {
+				ring.push(*first);
+			}
+		}
+		ring
+	})
+}
+
+/// Create a polygon from closed rings, dropping the closing points as they are implied on the map.
+pub(crate) fn polygon_from_rings(rings: Vec<Vec<Location>>) -> Polygon {
+	let mut rings = rings.into_iter().map(|mut ring| {
+		if let (Some(first), Some(last)) = (ring.first(), ring.last()) {
+			if ring.len() > 1 && (first.lat, first.lon) == (last.lat, last.lon) {
+				ring.pop();
+			}
+		}
+		ring
+	});
+	let polygon = Polygon::new(rings.next().unwrap_or_default());
+	rings.fold(polygon, Polygon::path)
+}
+
+/// An error that occurred while parsing WKT, WKB or GeoJSON.
+#[derive(Debug, Clone)]
+pub enum Error {
+	/// Invalid WKT.
+	Wkt(String),
+	/// Invalid WKB.
+	Wkb(String),
+	/// Invalid GeoJSON.
+	GeoJson(String),
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Wkt(msg) => write!(f, "invalid WKT: {msg}"),
+			Error::Wkb(msg) => write!(f, "invalid WKB: {msg}"),
+			Error::GeoJson(msg) => write!(f, "invalid GeoJSON: {msg}"),
+		}
+	}
+}
+
+impl StdError for Error {}
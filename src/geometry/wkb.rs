@@ -0,0 +1,269 @@
+use crate::geometry::{closed_rings, polygon_from_rings, Error, Geometry, MAX_DEPTH};
+use crate::google::{Marker, Polygon, Polyline};
+use crate::Location;
+
+const POINT: u32 = 1;
+const LINESTRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTIPOINT: u32 = 4;
+const MULTILINESTRING: u32 = 5;
+const MULTIPOLYGON: u32 = 6;
+const GEOMETRYCOLLECTION: u32 = 7;
+
+// EWKB flags
+const EWKB_Z: u32 = 0x8000_0000;
+const EWKB_M: u32 = 0x4000_0000;
+const EWKB_SRID: u32 = 0x2000_0000;
+
+pub(super) fn parse(wkb: &[u8]) -> Result<Geometry, Error> {
+	let mut reader = Reader {
+		buf: wkb,
+		pos: 0,
+		little_endian: true,
+		depth: 0,
+	};
+	let geometry = reader.geometry()?;
+	if reader.pos == wkb.len() {
+		Ok(geometry)
+	} else {
+		Err(Error::Wkb(format!(
+			"{} trailing bytes",
+			wkb.len() - reader.pos
+		)))
+	}
+}
+
+struct Reader<'a> {
+	buf: &'a [u8],
+	pos: usize,
+	little_endian: bool,
+	/// The number of enclosing geometry collections.
+	depth: usize,
+}
+
+impl Reader<'_> {
+	fn bytes<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+		let bytes = self
+			.buf
+			.get(self.pos..self.pos + N)
+			.ok_or_else(|| Error::Wkb(format!("unexpected end of input at offset {}", self.pos)))?;
+		self.pos += N;
+		Ok(bytes.try_into().expect("slice has the requested length"))
+	}
+
+	fn u32(&mut self) -> Result<u32, Error> {
+		let bytes = self.bytes()?;
+		Ok(if self.little_endian {
+			u32::from_le_bytes(bytes)
+		} else {
+			u32::from_be_bytes(bytes)
+		})
+	}
+
+	fn f64(&mut self) -> Result<f64, Error> {
+		let bytes = self.bytes()?;
+		Ok(if self.little_endian {
+			f64::from_le_bytes(bytes)
+		} else {
+			f64::from_be_bytes(bytes)
+		})
+	}
+
+	fn coord(&mut self, dims: usize) -> Result<Location, Error> {
+		let x = self.f64()?;
+		let y = self.f64()?;
+		for _ in 2..dims {
+			self.f64()?;
+		}
+		Ok(Location::new(y, x))
+	}
+
+	fn list<T>(
+		&mut self,
+		mut item: impl FnMut(&mut Self) -> Result<T, Error>,
+	) -> Result<Vec<T>, Error> {
+		let len = self.u32()?;
+		// don't trust the length for the allocation, the input may be truncated
+		let mut items = Vec::with_capacity(len.min(1024) as usize);
+		for _ in 0..len {
+			items.push(item(self)?);
+		}
+		Ok(items)
+	}
+
+	/// Parse the byte order and type header. Returns the geometry type and the number of dimensions.
+	fn header(&mut self) -> Result<(u32, usize), Error> {
+		self.little_endian = match self.bytes::<1>()? {
+			[0] => false,
+			[1] => true,
+			[b] => return Err(Error::Wkb(format!("invalid byte order {b}"))),
+		};
+
+		let header = self.u32()?;
+		if header & EWKB_SRID != 0 {
+			self.u32()?;
+		}
+		let mut dims = 2;
+		if header & EWKB_Z != 0 {
+			dims += 1;
+		}
+		if header & EWKB_M != 0 {
+			dims += 1;
+		}
+		// ISO WKB encodes the dimensions in the thousands: 1000 = Z, 2000 = M, 3000 = ZM
+		let kind = header & 0x0fff_ffff;
+		dims += match kind / 1000 {
+			0 => 0,
+			1 | 2 => 1,
+			3 => 2,
+			_ => return Err(Error::Wkb(format!("unknown geometry type {kind}"))),
+		};
+
+		Ok((kind % 1000, dims))
+	}
+
+	/// Parse the header of a member of a multi-geometry. Returns the number of dimensions.
+	fn member(&mut self, expected: u32) -> Result<usize, Error> {
+		let offset = self.pos;
+		match self.header()? {
+			(kind, dims) if kind == expected => Ok(dims),
+			(kind, _) => Err(Error::Wkb(format!(
+				"expected geometry type {expected}, found {kind} at offset {offset}"
+			))),
+		}
+	}
+
+	fn line(&mut self, dims: usize) -> Result<Polyline, Error> {
+		self.list(|r| r.coord(dims)).map(Polyline::new)
+	}
+
+	fn polygon(&mut self, dims: usize) -> Result<Polygon, Error> {
+		self.list(|r| r.list(|r| r.coord(dims)))
+			.map(polygon_from_rings)
+	}
+
+	fn geometry(&mut self) -> Result<Geometry, Error> {
+		let (kind, dims) = self.header()?;
+		match kind {
+			POINT => {
+				let point = self.coord(dims)?;
+				// an empty point is encoded as NaN coordinates
+				if point.lat.is_nan() && point.lon.is_nan() {
+					Ok(Geometry::MultiPoint(Vec::new()))
+				} else {
+					Ok(Geometry::Point(Marker::new(point)))
+				}
+			}
+			LINESTRING => self.line(dims).map(Geometry::LineString),
+			POLYGON => self.polygon(dims).map(Geometry::Polygon),
+			MULTIPOINT => self
+				.list(|r| {
+					let dims = r.member(POINT)?;
+					r.coord(dims).map(Marker::new)
+				})
+				.map(Geometry::MultiPoint),
+			MULTILINESTRING => self
+				.list(|r| {
+					let dims = r.member(LINESTRING)?;
+					r.line(dims)
+				})
+				.map(Geometry::MultiLineString),
+			MULTIPOLYGON => self
+				.list(|r| {
+					let dims = r.member(POLYGON)?;
+					r.polygon(dims)
+				})
+				.map(Geometry::MultiPolygon),
+			GEOMETRYCOLLECTION => {
+				if self.depth == MAX_DEPTH {
+					return Err(Error::Wkb(format!(
+						"geometry collections nested deeper than {MAX_DEPTH} levels"
+					)));
+				}
+				self.depth += 1;
+				let geometries = self.list(Reader::geometry)?;
+				self.depth -= 1;
+				Ok(Geometry::GeometryCollection(geometries))
+			}
+			_ => Err(Error::Wkb(format!("unknown geometry type {kind}"))),
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+pub(super) fn write(geometry: &Geometry, buf: &mut Vec<u8>) {
+	fn header(buf: &mut Vec<u8>, kind: u32) {
+		buf.push(1);
+		buf.extend_from_slice(&kind.to_le_bytes());
+	}
+
+	fn len(buf: &mut Vec<u8>, len: usize) {
+		// documented on `Geometry::to_wkb`
+		let len = u32::try_from(len).expect("too many elements for WKB");
+		buf.extend_from_slice(&len.to_le_bytes());
+	}
+
+	fn coord(buf: &mut Vec<u8>, loc: Location) {
+		buf.extend_from_slice(&loc.lon.to_le_bytes());
+		buf.extend_from_slice(&loc.lat.to_le_bytes());
+	}
+
+	fn point(buf: &mut Vec<u8>, marker: &Marker) {
+		header(buf, POINT);
+		coord(buf, marker.position);
+	}
+
+	fn line(buf: &mut Vec<u8>, coords: &[Location]) {
+		len(buf, coords.len());
+		for loc in coords {
+			coord(buf, *loc);
+		}
+	}
+
+	fn polygon(buf: &mut Vec<u8>, polygon: &Polygon) {
+		header(buf, POLYGON);
+		len(buf, polygon.paths.len());
+		for ring in closed_rings(polygon) {
+			line(buf, &ring);
+		}
+	}
+
+	match geometry {
+		Geometry::Point(m) => point(buf, m),
+		Geometry::LineString(p) => {
+			header(buf, LINESTRING);
+			line(buf, &p.path);
+		}
+		Geometry::Polygon(p) => polygon(buf, p),
+		Geometry::MultiPoint(m) => {
+			header(buf, MULTIPOINT);
+			len(buf, m.len());
+			for m in m {
+				point(buf, m);
+			}
+		}
+		Geometry::MultiLineString(p) => {
+			header(buf, MULTILINESTRING);
+			len(buf, p.len());
+			for p in p {
+				header(buf, LINESTRING);
+				line(buf, &p.path);
+			}
+		}
+		Geometry::MultiPolygon(p) => {
+			header(buf, MULTIPOLYGON);
+			len(buf, p.len());
+			for p in p {
+				polygon(buf, p);
+			}
+		}
+		Geometry::GeometryCollection(g) => {
+			header(buf, GEOMETRYCOLLECTION);
+			len(buf, g.len());
+			for g in g {
+				write(g, buf);
+			}
+		}
+	}
+}
@@ -0,0 +1,341 @@
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+use crate::geometry::{closed_rings, polygon_from_rings, Error, Geometry, MAX_DEPTH};
+use crate::google::{Marker, Polygon, Polyline};
+use crate::Location;
+
+pub(super) fn parse(s: &str) -> Result<Geometry, Error> {
+	// EWKT, e.g. `SRID=4326;POINT(1 2)`
+	let s = match s.trim_start().split_once(';') {
+		Some((srid, rest)) if srid.trim().to_ascii_uppercase().starts_with("SRID=") => rest,
+		_ => s,
+	};
+
+	let mut parser = Parser {
+		src: s,
+		chars: s.char_indices().peekable(),
+		depth: 0,
+	};
+	let geometry = parser.geometry()?;
+	match parser.next_token()? {
+		Token::End => Ok(geometry),
+		token => Err(parser.unexpected(&token, "end of input")),
+	}
+}
+
+impl FromStr for Geometry {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		parse(s)
+	}
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+	Word(&'a str),
+	Number(f64),
+	LParen,
+	RParen,
+	Comma,
+	End,
+}
+
+impl Display for Token<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Token::Word(w) => write!(f, "`{w}`"),
+			Token::Number(n) => write!(f, "`{n}`"),
+			Token::LParen => f.write_str("`(`"),
+			Token::RParen => f.write_str("`)`"),
+			Token::Comma => f.write_str("`,`"),
+			Token::End => f.write_str("end of input"),
+		}
+	}
+}
+
+struct Parser<'a> {
+	src: &'a str,
+	chars: Peekable<CharIndices<'a>>,
+	/// The number of enclosing geometry collections.
+	depth: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn unexpected(&self, found: &Token<'_>, expected: &str) -> Error {
+		Error::Wkt(format!(
+			"expected {expected}, found {found} in {:?}",
+			self.src
+		))
+	}
+
+	fn next_token(&mut self) -> Result<Token<'a>, Error> {
+		while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+
+		let Some((start, c)) = self.chars.next() else {
+			return Ok(Token::End);
+		};
+		let mut end = start + c.len_utf8();
+		let mut take_while = |chars: &mut Peekable<CharIndices<'a>>, pred: fn(char) -> bool| {
+			while let Some((i, c)) = chars.next_if(|&(_, c)| pred(c)) {
+				end = i + c.len_utf8();
+			}
+			end
+		};
+
+		match c {
+			'(' => Ok(Token::LParen),
+			')' => Ok(Token::RParen),
+			',' => Ok(Token::Comma),
+			c if c.is_ascii_alphabetic() => {
+				let end = take_while(&mut self.chars, |c| c.is_ascii_alphanumeric());
+				Ok(Token::Word(&self.src[start..end]))
+			}
+			c if c.is_ascii_digit() || matches!(c, '-' | '+' | '.') => {
+				let end = take_while(&mut self.chars, |c| {
+					c.is_ascii_alphanumeric() || matches!(c, '-' | '+' | '.')
+				});
+				let number = &self.src[start..end];
+				number
+					.parse()
+					.map(Token::Number)
+					.map_err(|_| Error::Wkt(format!("invalid number {number:?}")))
+			}
+			c => Err(Error::Wkt(format!("unexpected character {c:?}"))),
+		}
+	}
+
+	fn expect(&mut self, expected: &Token<'_>) -> Result<(), Error> {
+		let token = self.next_token()?;
+		if &token == expected {
+			Ok(())
+		} else {
+			Err(self.unexpected(&token, &expected.to_string()))
+		}
+	}
+
+	/// Parse the rest of a comma separated list whose opening parenthesis was already consumed.
+	fn list<T>(
+		&mut self,
+		mut item: impl FnMut(&mut Self) -> Result<T, Error>,
+	) -> Result<Vec<T>, Error> {
+		let mut items = vec![item(self)?];
+		loop {
+			match self.next_token()? {
+				Token::Comma => items.push(item(self)?),
+				Token::RParen => return Ok(items),
+				token => return Err(self.unexpected(&token, "`,` or `)`")),
+			}
+		}
+	}
+
+	/// Parse the optional `Z`, `M` or `ZM` modifier and the opening parenthesis. Returns `false` for `EMPTY`.
+	fn header(&mut self) -> Result<bool, Error> {
+		loop {
+			match self.next_token()? {
+				Token::LParen => return Ok(true),
+				Token::Word(w) if w.eq_ignore_ascii_case("EMPTY") => return Ok(false),
+				Token::Word(w) if ["Z", "M", "ZM"].iter().any(|m| w.eq_ignore_ascii_case(m)) => {}
+				token => return Err(self.unexpected(&token, "`(` or `EMPTY`")),
+			}
+		}
+	}
+
+	fn peek_token(&mut self) -> Token<'a> {
+		let chars = self.chars.clone();
+		let token = self.next_token().unwrap_or(Token::End);
+		self.chars = chars;
+		token
+	}
+
+	/// Parse a coordinate, ignoring the Z and M values if present.
+	fn coord(&mut self) -> Result<Location, Error> {
+		let number = |p: &mut Self| match p.next_token()? {
+			Token::Number(n) => Ok(n),
+			token => Err(p.unexpected(&token, "a number")),
+		};
+		let x = number(self)?;
+		let y = number(self)?;
+		for _ in 0..2 {
+			if matches!(self.peek_token(), Token::Number(_)) {
+				number(self)?;
+			}
+		}
+		Ok(Location::new(y, x))
+	}
+
+	/// Parse the opening parenthesis of a member of a geometry. Returns `false` for `EMPTY`.
+	fn member(&mut self) -> Result<bool, Error> {
+		match self.next_token()? {
+			Token::LParen => Ok(true),
+			Token::Word(w) if w.eq_ignore_ascii_case("EMPTY") => Ok(false),
+			token => Err(self.unexpected(&token, "`(` or `EMPTY`")),
+		}
+	}
+
+	fn coords(&mut self) -> Result<Vec<Location>, Error> {
+		if !self.member()? {
+			return Ok(Vec::new());
+		}
+		self.list(Parser::coord)
+	}
+
+	fn rings(&mut self) -> Result<Vec<Vec<Location>>, Error> {
+		if !self.member()? {
+			return Ok(Vec::new());
+		}
+		self.list(Parser::coords)
+	}
+
+	fn geometry(&mut self) -> Result<Geometry, Error> {
+		let kind = match self.next_token()? {
+			Token::Word(w) => w.to_ascii_uppercase(),
+			token => return Err(self.unexpected(&token, "a geometry type")),
+		};
+
+		if !self.header()? {
+			return match kind.as_str() {
+				"POINT" | "MULTIPOINT" => Ok(Geometry::MultiPoint(Vec::new())),
+				"LINESTRING" => Ok(Geometry::LineString(Polyline::new(Vec::<Location>::new()))),
+				"POLYGON" | "MULTIPOLYGON" => Ok(Geometry::MultiPolygon(Vec::new())),
+				"MULTILINESTRING" => Ok(Geometry::MultiLineString(Vec::new())),
+				"GEOMETRYCOLLECTION" => Ok(Geometry::GeometryCollection(Vec::new())),
+				_ => Err(Error::Wkt(format!("unknown geometry type {kind:?}"))),
+			};
+		}
+
+		match kind.as_str() {
+			"POINT" => {
+				let point = self.coord()?;
+				self.expect(&Token::RParen)?;
+				Ok(Geometry::Point(Marker::new(point)))
+			}
+			"LINESTRING" => Ok(Geometry::LineString(Polyline::new(
+				self.list(Parser::coord)?,
+			))),
+			"POLYGON" => Ok(Geometry::Polygon(polygon_from_rings(
+				self.list(Parser::coords)?,
+			))),
+			"MULTIPOINT" => {
+				// both `MULTIPOINT(1 2, 3 4)` and `MULTIPOINT((1 2), (3 4))` are allowed
+				let points = self.list(|p| {
+					if p.peek_token() == Token::LParen {
+						p.next_token()?;
+						let point = p.coord()?;
+						p.expect(&Token::RParen)?;
+						Ok(point)
+					} else {
+						p.coord()
+					}
+				})?;
+				Ok(Geometry::MultiPoint(
+					points.into_iter().map(Marker::new).collect(),
+				))
+			}
+			"MULTILINESTRING" => Ok(Geometry::MultiLineString(
+				self.list(Parser::coords)?
+					.into_iter()
+					.map(Polyline::new)
+					.collect(),
+			)),
+			"MULTIPOLYGON" => Ok(Geometry::MultiPolygon(
+				self.list(Parser::rings)?
+					.into_iter()
+					.map(polygon_from_rings)
+					.collect(),
+			)),
+			"GEOMETRYCOLLECTION" => {
+				if self.depth == MAX_DEPTH {
+					return Err(Error::Wkt(format!(
+						"geometry collections nested deeper than {MAX_DEPTH} levels"
+					)));
+				}
+				self.depth += 1;
+				let geometries = self.list(Parser::geometry)?;
+				self.depth -= 1;
+				Ok(Geometry::GeometryCollection(geometries))
+			}
+			_ => Err(Error::Wkt(format!("unknown geometry type {kind:?}"))),
+		}
+	}
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// The coordinates of a point, line or ring, `EMPTY` if there are none.
+struct Coords<'a>(&'a [Location]);
+
+impl Display for Coords<'_> {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		if self.0.is_empty() {
+			return f.write_str("EMPTY");
+		}
+		f.write_str("(")?;
+		for (i, loc) in self.0.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{} {}", loc.lon, loc.lat)?;
+		}
+		f.write_str(")")
+	}
+}
+
+/// Write a parenthesized list, or `empty` if there are no items: ` EMPTY` after a geometry type, `EMPTY` as a member.
+fn write_list<T>(
+	f: &mut Formatter<'_>,
+	empty: &str,
+	items: impl IntoIterator<Item = T>,
+	mut item: impl FnMut(&mut Formatter<'_>, T) -> fmt::Result,
+) -> fmt::Result {
+	let mut items = items.into_iter().peekable();
+	if items.peek().is_none() {
+		return f.write_str(empty);
+	}
+	f.write_str("(")?;
+	for (i, it) in items.enumerate() {
+		if i > 0 {
+			f.write_str(", ")?;
+		}
+		item(f, it)?;
+	}
+	f.write_str(")")
+}
+
+/// Write the rings of a polygon, leaving out empty rings.
+fn write_rings(f: &mut Formatter<'_>, empty: &str, polygon: &Polygon) -> fmt::Result {
+	let rings = closed_rings(polygon).filter(|ring| !ring.is_empty());
+	write_list(f, empty, rings, |f, ring| Coords(&ring).fmt(f))
+}
+
+impl Display for Geometry {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Geometry::Point(m) => write!(f, "POINT{}", Coords(&[m.position])),
+			Geometry::LineString(p) if p.path.is_empty() => f.write_str("LINESTRING EMPTY"),
+			Geometry::LineString(p) => write!(f, "LINESTRING{}", Coords(&p.path)),
+			Geometry::Polygon(p) => {
+				f.write_str("POLYGON")?;
+				write_rings(f, " EMPTY", p)
+			}
+			Geometry::MultiPoint(m) => {
+				f.write_str("MULTIPOINT")?;
+				write_list(f, " EMPTY", m, |f, m| Coords(&[m.position]).fmt(f))
+			}
+			Geometry::MultiLineString(p) => {
+				f.write_str("MULTILINESTRING")?;
+				write_list(f, " EMPTY", p, |f, p| Coords(&p.path).fmt(f))
+			}
+			Geometry::MultiPolygon(p) => {
+				f.write_str("MULTIPOLYGON")?;
+				write_list(f, " EMPTY", p, |f, p| write_rings(f, "EMPTY", p))
+			}
+			Geometry::GeometryCollection(g) => {
+				f.write_str("GEOMETRYCOLLECTION")?;
+				write_list(f, " EMPTY", g, |f, g| g.fmt(f))
+			}
+		}
+	}
+}
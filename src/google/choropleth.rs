@@ -0,0 +1,353 @@
+use std::fmt::{self, Formatter};
+
+use crate::google::style::{Color, Colormap};
+use crate::google::utils::{escape_html, JavaScript};
+use crate::google::{fmt_part, Legend, Polygon, Shape, MAP_IDENT};
+use crate::BoundingBox;
+
+/// How the values of a [`Choropleth`] are divided into classes.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Binning {
+	/// The given number of classes, each spanning the same range of values.
+	EqualInterval(usize),
+	/// The given number of classes, each containing the same number of values.
+	Quantile(usize),
+	/// The given number of classes, minimizing the variance within each class
+	/// ([Jenks natural breaks](https://en.wikipedia.org/wiki/Jenks_natural_breaks_optimization)).
+	Jenks(usize),
+	/// Classes separated by the given thresholds. A value equal to a threshold belongs to the upper class.
+	Custom(Vec<f64>),
+}
+
+/// Polygons colored by a numeric value each.
+///
+/// The values are divided into classes (see [`Binning`]), and every class gets a fill color from the color scale.
+/// Hovering a region shows its name and value. [`legend`](Choropleth::legend) returns a matching [`Legend`].
+///
+/// Only the fill color of the polygons is changed, the fill opacity defaults to `0.7`. Regions with a value which
+/// isn't finite keep their fill color. The event listeners ([`Polygon::on`]) and IDs ([`Polygon::id`]) of the polygons
+/// are kept; a region with an ID is in `__shapes` as described in [`Marker::id`](crate::google::Marker::id), but isn't
+/// declared as a variable.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::choropleth::{Binning, Choropleth};
+/// use mapplot::google::style::Color;
+/// use mapplot::google::{GoogleMap, Polygon};
+///
+/// let regions = (0..5).map(|i| {
+///     let lon = f64::from(i);
+///     (Polygon::new([(46.0, lon), (47.0, lon), (47.0, lon + 1.0), (46.0, lon + 1.0)]), f64::from(i * i))
+/// });
+///
+/// let choropleth = Choropleth::new(regions)
+///     .names(["A", "B", "C", "D", "E"])
+///     .binning(Binning::EqualInterval(4))
+///     .colors([Color::White, Color::Red])
+///     .title("Population");
+///
+/// assert_eq!(choropleth.breaks(), vec![4.0, 8.0, 12.0]);
+///
+/// let html = GoogleMap::new((46.5, 2.5), 7, "<your-apikey-here>")
+///     .legend(choropleth.legend())
+///     .draw(choropleth)
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+///
+/// Regions which respond to clicks:
+/// ```
+/// use mapplot::google::choropleth::Choropleth;
+/// use mapplot::google::{Action, Event, GoogleMap, Polygon};
+///
+/// let france = Polygon::new([(51.1, 2.5), (42.3, 3.2), (43.4, -1.8), (48.4, -4.8)])
+///     .id("france")
+///     .on(Event::Click, Action::Script("alert(\"France\");".to_string()));
+///
+/// let html = GoogleMap::new((46.5, 2.5), 5, "<your-apikey-here>")
+///     .draw(Choropleth::new([(france, 67.8)]))
+///     .to_string();
+///
+/// assert!(html.contains(r#"[__id("france", __on(new google.maps.Polygon("#));
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Choropleth {
+	regions: Vec<(Polygon, f64)>,
+	names: Vec<String>,
+	binning: Binning,
+	colors: Vec<Color>,
+	title: Option<String>,
+	precision: Option<usize>,
+}
+
+impl Choropleth {
+	/// Create a new choropleth from polygons and their values.
+	#[must_use]
+	pub fn new(regions: impl IntoIterator<Item = (Polygon, f64)>) -> Self {
+		Choropleth {
+			regions: regions.into_iter().collect(),
+			names: Vec::new(),
+			binning: Binning::Quantile(5),
+			colors: vec![
+				Color::RGB(0xff, 0xff, 0xb2),
+				Color::RGB(0xfe, 0xcc, 0x5c),
+				Color::RGB(0xfd, 0x8d, 0x3c),
+				Color::RGB(0xf0, 0x3b, 0x20),
+				Color::RGB(0xbd, 0x00, 0x26),
+			],
+			title: None,
+			precision: None,
+		}
+	}
+
+	/// The names of the regions, in the same order as the regions, shown when hovering a region.
+	#[must_use]
+	pub fn names(mut self, names: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+		self.names = names.into_iter().map(|n| n.as_ref().to_string()).collect();
+		self
+	}
+
+	/// How the values are divided into classes. Defaults to `Binning::Quantile(5)`.
+	#[must_use]
+	pub fn binning(mut self, value: Binning) -> Self {
+		self.binning = value;
+		self
+	}
+
+	/// The color scale, from the lowest to the highest class, e.g. `Colormap::viridis().colors(5)`. If the number of
+	/// colors differs from the number of classes, the colors are interpolated. Defaults to five shades from yellow to
+	/// red.
+	#[must_use]
+	pub fn colors(mut self, value: impl IntoIterator<Item = Color>) -> Self {
+		self.colors = value.into_iter().collect();
+		self
+	}
+
+	/// The title of the legend.
+	#[must_use]
+	pub fn title(mut self, value: impl AsRef<str>) -> Self {
+		self.title = Some(value.as_ref().to_string());
+		self
+	}
+
+	/// The number of decimal places of the values shown in the legend and when hovering a region. By default, values
+	/// are shown with as many decimal places as needed.
+	#[must_use]
+	pub fn precision(mut self, value: usize) -> Self {
+		self.precision = Some(value);
+		self
+	}
+
+	/// The thresholds between the classes, in ascending order.
+	#[must_use]
+	pub fn breaks(&self) -> Vec<f64> {
+		let mut values: Vec<f64> = self.values().collect();
+		values.sort_by(f64::total_cmp);
+
+		let mut breaks = match &self.binning {
+			Binning::EqualInterval(n) => match (values.first(), values.last()) {
+				(Some(&min), Some(&max)) => (1..*n)
+					.map(|i| min + (max - min) * as_f64(i) / as_f64(*n))
+					.collect(),
+				_ => Vec::new(),
+			},
+			Binning::Quantile(n) => (1..*n)
+				.filter_map(|i| values.get(i * values.len() / n).copied())
+				.collect(),
+			Binning::Jenks(n) => jenks(&values, *n),
+			Binning::Custom(thresholds) => {
+				let mut thresholds = thresholds.clone();
+				thresholds.sort_by(f64::total_cmp);
+				thresholds
+			}
+		};
+		breaks.dedup();
+		breaks
+	}
+
+	/// A legend with one entry per class.
+	#[must_use]
+	pub fn legend(&self) -> Legend {
+		let breaks = self.breaks();
+		let mut values: Vec<f64> = self.values().collect();
+		values.sort_by(f64::total_cmp);
+
+		let mut bounds = Vec::with_capacity(breaks.len() + 2);
+		bounds.push(values.first().copied().unwrap_or(f64::NEG_INFINITY));
+		bounds.extend(&breaks);
+		bounds.push(values.last().copied().unwrap_or(f64::INFINITY));
+
+		let mut legend = Legend::new();
+		if let Some(title) = &self.title {
+			legend = legend.title(title);
+		}
+		for (i, bounds) in bounds.windows(2).enumerate() {
+			let label = format!("{} – {}", self.format(bounds[0]), self.format(bounds[1]));
+			legend = legend.swatch(self.color(i, breaks.len() + 1), label);
+		}
+		legend
+	}
+
+	/// The polygons filled with the color of their class.
+	#[must_use]
+	pub fn polygons(&self) -> Vec<Polygon> {
+		let breaks = self.breaks();
+		self.regions
+			.iter()
+			.map(|(polygon, value)| {
+				let mut polygon = polygon.clone();
+				if value.is_finite() {
+					let class = breaks.iter().take_while(|b| *b <= value).count();
+					polygon.style.fill_color = Some(self.color(class, breaks.len() + 1));
+					polygon.style.fill_opacity = polygon.style.fill_opacity.or(Some(0.7));
+				}
+				polygon
+			})
+			.collect()
+	}
+
+	fn values(&self) -> impl Iterator<Item = f64> + '_ {
+		self.regions
+			.iter()
+			.map(|(_, v)| *v)
+			.filter(|v| v.is_finite())
+	}
+
+	/// The color of the given class out of `count` classes.
+	fn color(&self, class: usize, count: usize) -> Color {
+		if self.colors.len() == count || count == 1 {
+			return self.colors.get(class).copied().unwrap_or(Color::Gray);
+		}
+		if self.colors.is_empty() {
+			return Color::Gray;
+		}
+		Colormap::new(self.colors.iter().copied()).at(as_f64(class) / as_f64(count - 1))
+	}
+
+	fn format(&self, value: f64) -> String {
+		match self.precision {
+			Some(precision) => format!("{value:.precision$}"),
+			None => value.to_string(),
+		}
+	}
+}
+
+impl Shape for Choropleth {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("(function () {\n")?;
+		f.write_str(
+			"\t\t\tconst tooltip = new google.maps.InfoWindow({ disableAutoPan: true });\n",
+		)?;
+
+		// [polygon, tooltip]
+		f.write_str("\t\t\tconst regions = [")?;
+		for (i, (polygon, (_, value))) in self.polygons().iter().zip(&self.regions).enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			f.write_str("[")?;
+			fmt_part(f, polygon)?;
+			f.write_str(", ")?;
+			let value = escape_html(&self.format(*value));
+			match self.names.get(i) {
+				Some(name) => format!("<b>{}</b>: {value}", escape_html(name)).fmt_js(f)?,
+				None => value.fmt_js(f)?,
+			}
+			f.write_str("]")?;
+		}
+		f.write_str("];\n")?;
+
+		write!(
+			f,
+			r#"			for (const [polygon, text] of regions) {{
+				polygon.addListener("mousemove", e => {{
+					tooltip.setContent(text);
+					tooltip.setPosition(e.latLng);
+					if (!tooltip.isOpen) tooltip.open({map});
+				}});
+				polygon.addListener("mouseout", () => tooltip.close());
+			}}
+			return regions.map(([polygon]) => polygon);
+		}})()"#,
+			map = MAP_IDENT.0,
+		)
+	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<crate::google::serde_shapes::ShapeRepr<'_>> {
+		Some(crate::google::serde_shapes::ShapeRepr::Choropleth(self))
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		self.regions
+			.iter()
+			.filter_map(|(polygon, _)| polygon.bounds())
+			.reduce(BoundingBox::union)
+	}
+
+	fn parts(&self) -> Vec<&dyn Shape> {
+		self.regions
+			.iter()
+			.map(|(polygon, _)| polygon as &dyn Shape)
+			.collect()
+	}
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn as_f64(n: usize) -> f64 {
+	n as f64
+}
+
+/// The lower bounds of all but the first of `k` classes of the sorted `values`, using the Fisher-Jenks algorithm.
+fn jenks(values: &[f64], k: usize) -> Vec<f64> {
+	let n = values.len();
+	let k = k.min(n);
+	if k < 2 {
+		return Vec::new();
+	}
+
+	// 1-based: lower[l][j] is the first value of the last class when dividing the first l values into j classes
+	let mut lower = vec![vec![1; k + 1]; n + 1];
+	let mut variance = vec![vec![f64::INFINITY; k + 1]; n + 1];
+	variance[1].fill(0.0);
+
+	for l in 2..=n {
+		let (mut sum, mut sum_squares, mut count) = (0.0, 0.0, 0.0);
+		let mut v = 0.0;
+		for m in 1..=l {
+			let first = l - m + 1;
+			let value = values[first - 1];
+			sum += value;
+			sum_squares += value * value;
+			count += 1.0;
+			v = sum_squares - sum * sum / count;
+			if first > 1 {
+				for j in 2..=k {
+					if variance[l][j] >= v + variance[first - 1][j - 1] {
+						lower[l][j] = first;
+						variance[l][j] = v + variance[first - 1][j - 1];
+					}
+				}
+			}
+		}
+		lower[l][1] = 1;
+		variance[l][1] = v;
+	}
+
+	let mut breaks = Vec::with_capacity(k - 1);
+	let mut last = n;
+	for j in (2..=k).rev() {
+		let first = lower[last][j];
+		if first < 2 {
+			break;
+		}
+		breaks.push(values[first - 1]);
+		last = first - 1;
+	}
+	breaks.reverse();
+	breaks
+}
@@ -0,0 +1,227 @@
+use std::fmt::{self, Formatter};
+
+use crate::google::style::Color;
+use crate::google::utils::{FormatterExt, JavaScript};
+use crate::google::{Marker, Shape, MAP_IDENT};
+use crate::BoundingBox;
+
+/// A large set of markers, grouped into clusters depending on the zoom level.
+///
+/// The markers are clustered in the browser by dividing the map into a grid of square cells, one cluster per cell, up
+/// to the zoom level [`max_zoom`](MarkerCluster::max_zoom). Only the markers and clusters within the visible area are
+/// created, updated whenever the map comes to rest, so tens of thousands of markers stay usable. Clusters are drawn as
+/// circles labeled with the number of markers, colored and sized by that number. Clicking a cluster zooms to its
+/// markers.
+///
+/// Only the positions, labels and titles of the markers are used; their [event listeners](Marker::on),
+/// [IDs](Marker::id) and [times](Marker::time) are ignored.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::Color;
+/// use mapplot::google::{GoogleMap, Marker, MarkerCluster};
+///
+/// let markers = (0..1000).map(|i| Marker::new((f64::from(i % 40), f64::from(i / 40))));
+///
+/// let html = GoogleMap::new((20.0, 12.0), 3, "<your-apikey-here>")
+///     .draw(MarkerCluster::new(markers).color_steps([(0, Color::Blue), (50, Color::Red)]))
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarkerCluster {
+	markers: Vec<Marker>,
+	grid_size: u32,
+	max_zoom: u8,
+	color_steps: Vec<(usize, Color)>,
+}
+
+impl MarkerCluster {
+	/// Create a new cluster of markers.
+	#[must_use]
+	pub fn new(markers: impl IntoIterator<Item = Marker>) -> Self {
+		MarkerCluster {
+			markers: markers.into_iter().collect(),
+			grid_size: 60,
+			max_zoom: 15,
+			color_steps: vec![
+				(0, Color::Blue),
+				(10, Color::Olive),
+				(100, Color::Red),
+				(1000, Color::Purple),
+			],
+		}
+	}
+
+	/// The size of a grid cell in pixels. Defaults to `60`.
+	#[must_use]
+	pub fn grid_size(mut self, value: u32) -> Self {
+		self.grid_size = value.max(1);
+		self
+	}
+
+	/// The maximum zoom level at which markers are clustered. All markers are shown individually when zoomed in
+	/// further. Defaults to `15`.
+	#[must_use]
+	pub fn max_zoom(mut self, value: u8) -> Self {
+		self.max_zoom = value;
+		self
+	}
+
+	/// The colors of the clusters. A cluster uses the color of the last step whose minimum count it reaches. Defaults
+	/// to blue, olive, red and purple for clusters of at least 0, 10, 100 and 1000 markers.
+	#[must_use]
+	pub fn color_steps(mut self, steps: impl IntoIterator<Item = (usize, Color)>) -> Self {
+		self.color_steps = steps.into_iter().collect();
+		self.color_steps.sort_by_key(|(count, _)| *count);
+		self
+	}
+
+	/// Write the positions, labels and titles of the markers and the colors of the clusters.
+	fn fmt_data(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		// [lat, lng, lat, lng, ...]
+		f.write_str("\t\t\tconst points = [")?;
+		for (i, marker) in self.markers.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{}, {}", marker.position.lat, marker.position.lon)?;
+		}
+		f.write_str("];\n")?;
+
+		// the labels and titles of the markers which have any, by index
+		f.write_str("\t\t\tconst details = {")?;
+		let detailed = self
+			.markers
+			.iter()
+			.enumerate()
+			.filter(|(_, m)| m.label.is_some() || m.title.is_some());
+		let mut any = false;
+		for (index, marker) in detailed {
+			f.write_str(if any { ", " } else { " " })?;
+			write!(f, "{index}: ")?;
+			f.write_object()
+				.entry_opt("label", &marker.label)
+				.entry_opt("title", &marker.title)
+				.finish()?;
+			any = true;
+		}
+		f.write_str(if any { " };\n" } else { "};\n" })?;
+
+		f.write_str("\t\t\tconst colors = [")?;
+		for (i, (count, color)) in self.color_steps.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "[{count}, ")?;
+			color.fmt_js(f)?;
+			f.write_str("]")?;
+		}
+		f.write_str("];\n")?;
+		Ok(())
+	}
+}
+
+impl Shape for MarkerCluster {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("(function () {\n")?;
+
+		self.fmt_data(f)?;
+
+		write!(
+			f,
+			r#"			const gridSize = {grid_size}, maxZoom = {max_zoom}, count = points.length / 2;
+			// Web Mercator world coordinates between 0 and 1
+			const project = (lat, lng) => {{
+				const sin = Math.min(Math.max(Math.sin(lat * Math.PI / 180), -0.9999), 0.9999);
+				return [(lng + 180) / 360, 0.5 - Math.log((1 + sin) / (1 - sin)) / (4 * Math.PI)];
+			}};
+			const xs = new Float64Array(count), ys = new Float64Array(count);
+			for (let i = 0; i < count; i++) [xs[i], ys[i]] = project(points[2 * i], points[2 * i + 1]);
+
+			const marker = i => new google.maps.Marker({{ ...details[i], map: {map}, position: {{ lat: points[2 * i], lng: points[2 * i + 1] }} }});
+			function cluster({{ count, lat, lng, south, west, north, east }}) {{
+				let color = colors.length > 0 ? colors[0][1] : "blue";
+				for (const [min, c] of colors) if (count >= min) color = c;
+				const cluster = new google.maps.Marker({{
+					map: {map},
+					position: {{ lat: lat / count, lng: lng / count }},
+					label: {{ text: String(count), color: "white", fontSize: "11px" }},
+					icon: {{
+						path: google.maps.SymbolPath.CIRCLE,
+						scale: 10 + 4 * Math.log10(count),
+						fillColor: color,
+						fillOpacity: 0.8,
+						strokeColor: color,
+						strokeOpacity: 0.4,
+						strokeWeight: 6,
+					}},
+					zIndex: google.maps.Marker.MAX_ZINDEX + count,
+				}});
+				cluster.addListener("click", () => {map}.fitBounds({{ south, west, north, east }}));
+				return cluster;
+			}}
+
+			// only the markers and clusters within the visible area, extended by a grid cell, are created
+			let shown = [], visible = true, removed = false;
+			function render() {{
+				shown.forEach(m => m.setMap(null));
+				shown = [];
+				const bounds = {map}.getBounds();
+				if (!visible || removed || !bounds) return;
+				const zoom = Math.round({map}.getZoom());
+				const cell = gridSize / (256 * 2 ** zoom);
+				const [west, north] = project(bounds.getNorthEast().lat(), bounds.getSouthWest().lng());
+				const [east, south] = project(bounds.getSouthWest().lat(), bounds.getNorthEast().lng());
+				const inView = i => ys[i] >= north - cell && ys[i] <= south + cell && (west <= east
+					? xs[i] >= west - cell && xs[i] <= east + cell
+					: xs[i] >= west - cell || xs[i] <= east + cell);
+
+				if (zoom > maxZoom) {{
+					for (let i = 0; i < count; i++) if (inView(i)) shown.push(marker(i));
+					return;
+				}}
+				const cells = new Map();
+				for (let i = 0; i < count; i++) {{
+					if (!inView(i)) continue;
+					const key = Math.floor(xs[i] / cell) + ":" + Math.floor(ys[i] / cell);
+					const lat = points[2 * i], lng = points[2 * i + 1];
+					let c = cells.get(key);
+					if (!c) cells.set(key, c = {{ first: i, count: 0, lat: 0, lng: 0, south: lat, west: lng, north: lat, east: lng }});
+					c.count++;
+					c.lat += lat;
+					c.lng += lng;
+					c.south = Math.min(c.south, lat);
+					c.west = Math.min(c.west, lng);
+					c.north = Math.max(c.north, lat);
+					c.east = Math.max(c.east, lng);
+				}}
+				for (const c of cells.values()) shown.push(c.count === 1 ? marker(c.first) : cluster(c));
+			}}
+			{map}.addListener("idle", render);
+			render();
+
+			// shown and hidden like a single shape, e.g. by the layer control
+			return {{
+				getVisible: () => visible,
+				setVisible: value => (visible = value, render()),
+				setMap: map => (removed = !map, render()),
+			}};
+		}})()"#,
+			grid_size = self.grid_size,
+			max_zoom = self.max_zoom,
+			map = MAP_IDENT.0,
+		)
+	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<crate::google::serde_shapes::ShapeRepr<'_>> {
+		Some(crate::google::serde_shapes::ShapeRepr::MarkerCluster(self))
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		BoundingBox::around(self.markers.iter().map(|m| &m.position))
+	}
+}
@@ -0,0 +1,254 @@
+use std::fmt::{self, Formatter};
+
+use crate::google::style::{Color, PolygonStyle, PolylineStyle};
+use crate::google::utils::JavaScript;
+use crate::google::{ControlPosition, MAP_IDENT};
+
+/// A kind of shape which can be drawn with the [`DrawingTools`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawingMode {
+	/// Click to place a marker.
+	Marker,
+	/// Click to add vertices, double-click to finish the line.
+	Polyline,
+	/// Click to add vertices, double-click to close the polygon.
+	Polygon,
+	/// Click two opposite corners.
+	Rectangle,
+	/// Click the center, then a point on the circumference.
+	Circle,
+}
+
+impl DrawingMode {
+	fn name(self) -> &'static str {
+		match self {
+			DrawingMode::Marker => "Marker",
+			DrawingMode::Polyline => "Polyline",
+			DrawingMode::Polygon => "Polygon",
+			DrawingMode::Rectangle => "Rectangle",
+			DrawingMode::Circle => "Circle",
+		}
+	}
+}
+
+/// A toolbar for drawing new shapes on the map.
+///
+/// Drawn shapes are editable and can be removed with a right click, pressing Escape cancels the current shape. They
+/// are included in the GeoJSON downloaded with the "Download edits" button, with IDs like `"drawn-1"`, and can be
+/// read back with [`Edits`](crate::google::edits::Edits).
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::{Color, PolygonStyle};
+/// use mapplot::google::{DrawingMode, DrawingTools, GoogleMap};
+///
+/// let tools = DrawingTools::new()
+///     .modes([DrawingMode::Marker, DrawingMode::Polygon, DrawingMode::Circle])
+///     .polygon_style(PolygonStyle::new().color(Color::Red).fill_opacity(0.2));
+///
+/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+///     .drawing_tools(tools)
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DrawingTools {
+	modes: Vec<DrawingMode>,
+	polyline_style: PolylineStyle,
+	polygon_style: PolygonStyle,
+	position: Option<ControlPosition>,
+}
+
+impl Default for DrawingTools {
+	fn default() -> Self {
+		let color = Color::RGB(0x1a, 0x73, 0xe8);
+		DrawingTools {
+			modes: vec![
+				DrawingMode::Marker,
+				DrawingMode::Polyline,
+				DrawingMode::Polygon,
+				DrawingMode::Rectangle,
+				DrawingMode::Circle,
+			],
+			polyline_style: PolylineStyle::new().color(color).width(3),
+			polygon_style: PolygonStyle::new()
+				.color(color)
+				.fill_opacity(0.3)
+				.stroke_width(2),
+			position: None,
+		}
+	}
+}
+
+impl DrawingTools {
+	/// Create new drawing tools for all kinds of shapes.
+	#[must_use]
+	pub fn new() -> Self {
+		DrawingTools::default()
+	}
+
+	/// The kinds of shapes which can be drawn, in the order of the toolbar. Defaults to all of them.
+	#[must_use]
+	pub fn modes(mut self, value: impl IntoIterator<Item = DrawingMode>) -> Self {
+		self.modes = value.into_iter().collect();
+		self
+	}
+
+	/// The style of drawn polylines. Defaults to a blue line.
+	#[must_use]
+	pub fn polyline_style(mut self, value: impl Into<PolylineStyle>) -> Self {
+		self.polyline_style = value.into();
+		self
+	}
+
+	/// The style of drawn polygons, rectangles and circles. Defaults to a translucent blue.
+	#[must_use]
+	pub fn polygon_style(mut self, value: impl Into<PolygonStyle>) -> Self {
+		self.polygon_style = value.into();
+		self
+	}
+
+	/// The position of the toolbar. Defaults to [`ControlPosition::TopLeft`].
+	#[must_use]
+	pub fn position(mut self, value: ControlPosition) -> Self {
+		self.position = Some(value);
+		self
+	}
+
+	/// The position, modes and styles as JS constants.
+	fn fmt_options(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("\t\t\tconst position = ")?;
+		self.position
+			.unwrap_or(ControlPosition::TopLeft)
+			.fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		f.write_str("\t\t\tconst modes = [")?;
+		for (i, mode) in self.modes.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			mode.name().fmt_js(f)?;
+		}
+		f.write_str("];\n")?;
+
+		f.write_str("\t\t\tconst polylineStyle = ")?;
+		self.polyline_style.fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		f.write_str("\t\t\tconst polygonStyle = ")?;
+		self.polygon_style.fmt_js(f)?;
+		f.write_str(";\n")
+	}
+
+	/// Write the toolbar, registering drawn shapes in the `[id, shape]` entries of the JS array `edits`.
+	pub(crate) fn fmt_control(&self, f: &mut Formatter<'_>, edits: &str) -> fmt::Result {
+		f.write_str("(function () {\n")?;
+		self.fmt_options(f)?;
+
+		write!(
+			f,
+			r#"			const map = {map}, doubleClickZoom = !map.get("disableDoubleClickZoom");
+			let mode = null, drawing = null, count = 0;
+
+			const control = document.createElement("div");
+			control.style.cssText = "display: flex; margin: 10px; background: white; border-radius: 2px; box-shadow: rgba(0, 0, 0, 0.3) 0 1px 4px -1px; font: 13px Roboto, Arial, sans-serif";
+			const buttons = new Map();
+			for (const m of [null, ...modes]) {{
+				const button = document.createElement("button");
+				button.textContent = m ?? "Pan";
+				button.style.cssText = "padding: 6px 10px; border: none; background: none; cursor: pointer";
+				button.addEventListener("click", () => select(m));
+				buttons.set(m, button);
+				control.append(button);
+			}}
+			map.controls[position].push(control);
+
+			function select(m) {{
+				cancel();
+				mode = m;
+				buttons.forEach((button, k) => button.style.fontWeight = k === mode ? "bold" : "normal");
+				map.setOptions({{ draggableCursor: mode ? "crosshair" : null, disableDoubleClickZoom: mode !== null || !doubleClickZoom }});
+			}}
+
+			function add(shape) {{
+				const id = "drawn-" + ++count;
+				shape.addListener("rightclick", () => {{
+					shape.setMap(null);
+					const i = {edits}.findIndex(([, s]) => s === shape);
+					if (i >= 0) {edits}.splice(i, 1);
+				}});
+				{edits}.push([id, shape]);
+			}}
+
+			function cancel() {{
+				if (drawing) drawing.shape.setMap(null);
+				drawing = null;
+			}}
+
+			function finish() {{
+				const {{ shape }} = drawing;
+				drawing = null;
+				if (shape.getPath) {{
+					// drop the vertex following the mouse and the duplicates added by the double click
+					const path = shape.getPath();
+					path.pop();
+					while (path.getLength() > 1 && path.getAt(path.getLength() - 1).equals(path.getAt(path.getLength() - 2))) path.pop();
+					if (path.getLength() < (shape instanceof google.maps.Polygon ? 3 : 2)) return shape.setMap(null);
+				}}
+				shape.setOptions({{ clickable: true, editable: true }});
+				add(shape);
+			}}
+
+			function distance(a, b) {{
+				const rad = Math.PI / 180, dLat = (b.lat() - a.lat()) * rad, dLng = (b.lng() - a.lng()) * rad;
+				const h = Math.sin(dLat / 2) ** 2 + Math.cos(a.lat() * rad) * Math.cos(b.lat() * rad) * Math.sin(dLng / 2) ** 2;
+				return 2 * 6371008.8 * Math.asin(Math.sqrt(h));
+			}}
+
+			function update(point) {{
+				const {{ shape, start }} = drawing;
+				if (shape instanceof google.maps.Rectangle) {{
+					shape.setBounds(new google.maps.LatLngBounds(
+						{{ lat: Math.min(start.lat(), point.lat()), lng: Math.min(start.lng(), point.lng()) }},
+						{{ lat: Math.max(start.lat(), point.lat()), lng: Math.max(start.lng(), point.lng()) }},
+					));
+				}} else if (shape instanceof google.maps.Circle) {{
+					shape.setRadius(distance(start, point));
+				}} else {{
+					const path = shape.getPath();
+					path.setAt(path.getLength() - 1, point);
+				}}
+			}}
+
+			map.addListener("click", e => {{
+				const point = e.latLng;
+				if (mode === "Marker") {{
+					add(new google.maps.Marker({{ map, position: point, draggable: true }}));
+				}} else if (mode === "Polyline" || mode === "Polygon") {{
+					if (drawing) drawing.shape.getPath().push(point);
+					else drawing = {{ shape: new google.maps[mode]({{ ...(mode === "Polyline" ? polylineStyle : polygonStyle), map, clickable: false, path: [point, point] }}) }};
+				}} else if (mode === "Rectangle" || mode === "Circle") {{
+					if (drawing) {{
+						update(point);
+						finish();
+					}} else if (mode === "Rectangle") {{
+						drawing = {{ start: point, shape: new google.maps.Rectangle({{ ...polygonStyle, map, clickable: false, bounds: new google.maps.LatLngBounds(point, point) }}) }};
+					}} else {{
+						drawing = {{ start: point, shape: new google.maps.Circle({{ ...polygonStyle, map, clickable: false, center: point, radius: 0 }}) }};
+					}}
+				}}
+			}});
+			map.addListener("mousemove", e => drawing && update(e.latLng));
+			map.addListener("dblclick", () => drawing && drawing.shape.getPath && finish());
+			document.addEventListener("keydown", e => e.key === "Escape" && cancel());
+			select(null);
+		}})()"#,
+			map = MAP_IDENT.0,
+		)
+	}
+}
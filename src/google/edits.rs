@@ -0,0 +1,446 @@
+//! Reading back the shapes edited by the user on the map.
+//!
+//! When a map contains editable or draggable shapes with an ID, or [`DrawingTools`](crate::google::DrawingTools),
+//! the page shows a "Download edits" button, which saves the current geometry of these shapes and of all drawn shapes
+//! as a GeoJSON `FeatureCollection`. Every feature has the ID of its shape as its `id` and an `id` and `shape` (e.g.
+//! `"Polyline"`) property. Markers are written as `Point`s, polylines as `LineString`s, polygons and rectangles as
+//! `Polygon`s and circles as `Point`s with a `radius` property in meters.
+//!
+//! # Examples
+//! ```
+//! use mapplot::google::edits::{EditedShape, Edits};
+//! use mapplot::google::{GoogleMap, Polyline};
+//!
+//! let mut route = Polyline::new([(51.507, -0.127), (52.48, -1.902)]).id("route").editable(true);
+//!
+//! let html = GoogleMap::new((52.0, -1.0), 7, "<your-apikey-here>")
+//!     .draw(route.clone())
+//!     .to_string();
+//! std::fs::write("map.html", html).unwrap();
+//!
+//! // the file downloaded from the page, e.g. read with `Edits::read("edits.geojson")`
+//! let geojson = r#"{
+//!     "type": "FeatureCollection",
+//!     "features": [{
+//!         "type": "Feature",
+//!         "id": "route",
+//!         "properties": { "id": "route", "shape": "Polyline" },
+//!         "geometry": { "type": "LineString", "coordinates": [[-0.127, 51.507], [-1.258, 51.752], [-1.902, 52.48]] }
+//!     }]
+//! }"#;
+//! let edits: Edits = geojson.parse().unwrap();
+//!
+//! // update the original shape, keeping its style
+//! assert!(route.apply_edits(&edits));
+//!
+//! // or rebuild the shapes from the edits alone
+//! assert!(matches!(edits.get("route"), Some(EditedShape::Polyline(_))));
+//! let html = GoogleMap::new((52.0, -1.0), 7, "<your-apikey-here>")
+//!     .draw_all(edits)
+//!     .to_string();
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use crate::google::utils::JavaScript;
+use crate::google::{
+	Circle, ControlPosition, Marker, Polygon, Polyline, Rectangle, Shape, MAP_IDENT,
+};
+use crate::json::{self, Value};
+use crate::{BoundingBox, Location};
+
+/// The shapes read from a GeoJSON file downloaded from the map.
+#[derive(Debug, Clone, Default)]
+pub struct Edits {
+	shapes: Vec<EditedShape>,
+}
+
+/// A shape rebuilt from its edited geometry, with its ID but without any other options.
+#[derive(Debug, Clone)]
+pub enum EditedShape {
+	Marker(Marker),
+	Polyline(Polyline),
+	Polygon(Polygon),
+	Rectangle(Rectangle),
+	Circle(Circle),
+}
+
+impl Edits {
+	/// Read and parse a GeoJSON file downloaded from the map.
+	///
+	/// # Errors
+	/// Returns an error if the file can't be read or isn't a GeoJSON `FeatureCollection` of edited shapes.
+	pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+		fs::read_to_string(path)?.parse()
+	}
+
+	/// The edited shape with the given ID.
+	#[must_use]
+	pub fn get(&self, id: &str) -> Option<&EditedShape> {
+		self.shapes.iter().find(|s| s.id() == id)
+	}
+
+	/// All edited shapes, in the order of the file.
+	pub fn iter(&self) -> impl Iterator<Item = &EditedShape> {
+		self.shapes.iter()
+	}
+}
+
+impl IntoIterator for Edits {
+	type Item = EditedShape;
+	type IntoIter = std::vec::IntoIter<EditedShape>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.shapes.into_iter()
+	}
+}
+
+impl FromStr for Edits {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let root = json::parse(s).map_err(Error::Json)?;
+		if root.get("type").and_then(Value::as_str) != Some("FeatureCollection") {
+			return Err(Error::Invalid("expected a FeatureCollection".to_string()));
+		}
+		let features = root
+			.get("features")
+			.and_then(Value::as_array)
+			.ok_or_else(|| Error::Invalid("missing features".to_string()))?;
+		let shapes = features
+			.iter()
+			.map(parse_feature)
+			.collect::<Result<_, _>>()?;
+		Ok(Edits { shapes })
+	}
+}
+
+impl EditedShape {
+	/// The ID of the shape.
+	#[must_use]
+	pub fn id(&self) -> &str {
+		self.shape_id().unwrap_or_default()
+	}
+}
+
+impl Shape for EditedShape {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			EditedShape::Marker(s) => s.fmt_js(f),
+			EditedShape::Polyline(s) => s.fmt_js(f),
+			EditedShape::Polygon(s) => s.fmt_js(f),
+			EditedShape::Rectangle(s) => s.fmt_js(f),
+			EditedShape::Circle(s) => s.fmt_js(f),
+		}
+	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<crate::google::serde_shapes::ShapeRepr<'_>> {
+		match self {
+			EditedShape::Marker(s) => s.serde_repr(),
+			EditedShape::Polyline(s) => s.serde_repr(),
+			EditedShape::Polygon(s) => s.serde_repr(),
+			EditedShape::Rectangle(s) => s.serde_repr(),
+			EditedShape::Circle(s) => s.serde_repr(),
+		}
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		match self {
+			EditedShape::Marker(s) => s.shape_id(),
+			EditedShape::Polyline(s) => s.shape_id(),
+			EditedShape::Polygon(s) => s.shape_id(),
+			EditedShape::Rectangle(s) => s.shape_id(),
+			EditedShape::Circle(s) => s.shape_id(),
+		}
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		match self {
+			EditedShape::Marker(s) => s.bounds(),
+			EditedShape::Polyline(s) => s.bounds(),
+			EditedShape::Polygon(s) => s.bounds(),
+			EditedShape::Rectangle(s) => s.bounds(),
+			EditedShape::Circle(s) => s.bounds(),
+		}
+	}
+}
+
+impl Marker {
+	/// Replace the position with the edited position of the marker with the same ID, keeping all other options.
+	/// Returns `false` if there is no edited marker with this ID.
+	pub fn apply_edits(&mut self, edits: &Edits) -> bool {
+		match self.id.as_deref().and_then(|id| edits.get(id)) {
+			Some(EditedShape::Marker(edited)) => {
+				self.position = edited.position;
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Polyline {
+	/// Replace the path with the edited path of the polyline with the same ID, keeping all other options. Returns
+	/// `false` if there is no edited polyline with this ID.
+	pub fn apply_edits(&mut self, edits: &Edits) -> bool {
+		match self.common.id.as_deref().and_then(|id| edits.get(id)) {
+			Some(EditedShape::Polyline(edited)) => {
+				self.path.clone_from(&edited.path);
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Polygon {
+	/// Replace the paths with the edited paths of the polygon with the same ID, keeping all other options. Returns
+	/// `false` if there is no edited polygon with this ID.
+	pub fn apply_edits(&mut self, edits: &Edits) -> bool {
+		match self.common.id.as_deref().and_then(|id| edits.get(id)) {
+			Some(EditedShape::Polygon(edited)) => {
+				self.paths.clone_from(&edited.paths);
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Rectangle {
+	/// Replace the bounds with the edited bounds of the rectangle with the same ID, keeping all other options.
+	/// Returns `false` if there is no edited rectangle with this ID.
+	pub fn apply_edits(&mut self, edits: &Edits) -> bool {
+		match self.common.id.as_deref().and_then(|id| edits.get(id)) {
+			Some(EditedShape::Rectangle(edited)) => {
+				self.bounds = edited.bounds;
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Circle {
+	/// Replace the center and radius with the edited ones of the circle with the same ID, keeping all other options.
+	/// Returns `false` if there is no edited circle with this ID.
+	pub fn apply_edits(&mut self, edits: &Edits) -> bool {
+		match self.common.id.as_deref().and_then(|id| edits.get(id)) {
+			Some(EditedShape::Circle(edited)) => {
+				self.center = edited.center;
+				self.radius = edited.radius;
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+/// An error that occurred while reading edits.
+#[derive(Debug)]
+pub enum Error {
+	/// The file couldn't be read.
+	Io(io::Error),
+	/// The file isn't valid JSON.
+	Json(String),
+	/// The JSON document isn't a GeoJSON `FeatureCollection` of edited shapes.
+	Invalid(String),
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Io(e) => write!(f, "failed to read edits: {e}"),
+			Error::Json(msg) => write!(f, "malformed GeoJSON: {msg}"),
+			Error::Invalid(msg) => write!(f, "invalid edits: {msg}"),
+		}
+	}
+}
+
+impl StdError for Error {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Error::Io(e) => Some(e),
+			Error::Json(_) | Error::Invalid(_) => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+fn parse_feature(feature: &Value) -> Result<EditedShape, Error> {
+	let properties = feature.get("properties");
+	let id = match feature
+		.get("id")
+		.or_else(|| properties.and_then(|p| p.get("id")))
+	{
+		Some(Value::String(id)) => id.clone(),
+		Some(Value::Number(id)) => id.to_string(),
+		_ => return Err(Error::Invalid("feature without an ID".to_string())),
+	};
+	let invalid = |msg: &str| Error::Invalid(format!("{msg} in feature {id:?}"));
+
+	let geometry = feature
+		.get("geometry")
+		.ok_or_else(|| invalid("missing geometry"))?;
+	let coordinates = geometry
+		.get("coordinates")
+		.ok_or_else(|| invalid("missing coordinates"))?;
+	let kind = match properties
+		.and_then(|p| p.get("shape"))
+		.and_then(Value::as_str)
+	{
+		Some(kind) => kind,
+		None => match geometry.get("type").and_then(Value::as_str) {
+			Some("LineString") => "Polyline",
+			Some("Polygon") => "Polygon",
+			Some("Point") if properties.and_then(|p| p.get("radius")).is_some() => "Circle",
+			Some("Point") => "Marker",
+			_ => return Err(invalid("unsupported geometry")),
+		},
+	};
+
+	let shape = match kind {
+		"Marker" => {
+			let position = parse_position(coordinates).ok_or_else(|| invalid("invalid Point"))?;
+			EditedShape::Marker(Marker::new(position).id(&id))
+		}
+		"Polyline" => {
+			let path = parse_path(coordinates).ok_or_else(|| invalid("invalid LineString"))?;
+			EditedShape::Polyline(Polyline::new(path).id(&id))
+		}
+		"Polygon" => {
+			let paths = parse_rings(coordinates).ok_or_else(|| invalid("invalid Polygon"))?;
+			let mut polygon = Polygon::new(Vec::<Location>::new()).id(&id);
+			polygon.paths = paths;
+			EditedShape::Polygon(polygon)
+		}
+		"Rectangle" => {
+			let corners: Vec<Location> = parse_rings(coordinates)
+				.ok_or_else(|| invalid("invalid Polygon"))?
+				.into_iter()
+				.flatten()
+				.collect();
+			let min = |f: fn(&Location) -> f64| corners.iter().map(f).fold(f64::INFINITY, f64::min);
+			let max =
+				|f: fn(&Location) -> f64| corners.iter().map(f).fold(f64::NEG_INFINITY, f64::max);
+			if corners.is_empty() {
+				return Err(invalid("empty Polygon"));
+			}
+			EditedShape::Rectangle(
+				Rectangle::new(
+					(min(|l| l.lat), min(|l| l.lon)),
+					(max(|l| l.lat), max(|l| l.lon)),
+				)
+				.id(&id),
+			)
+		}
+		"Circle" => {
+			let center = parse_position(coordinates).ok_or_else(|| invalid("invalid Point"))?;
+			let radius = properties
+				.and_then(|p| p.get("radius"))
+				.and_then(Value::as_f64)
+				.ok_or_else(|| invalid("missing radius"))?;
+			EditedShape::Circle(Circle::new(center, radius).id(&id))
+		}
+		kind => return Err(invalid(&format!("unknown shape {kind:?}"))),
+	};
+	Ok(shape)
+}
+
+/// A `[longitude, latitude]` position.
+fn parse_position(value: &Value) -> Option<Location> {
+	match value.as_array()? {
+		[lon, lat, ..] => Some(Location::new(lat.as_f64()?, lon.as_f64()?)),
+		_ => None,
+	}
+}
+
+fn parse_path(value: &Value) -> Option<Vec<Location>> {
+	value.as_array()?.iter().map(parse_position).collect()
+}
+
+/// The rings of a GeoJSON polygon, without the closing positions.
+fn parse_rings(value: &Value) -> Option<Vec<Vec<Location>>> {
+	value
+		.as_array()?
+		.iter()
+		.map(|ring| {
+			let mut ring = parse_path(ring)?;
+			if let [first, .., last] = ring[..] {
+				if (first.lat, first.lon) == (last.lat, last.lon) {
+					ring.pop();
+				}
+			}
+			Some(ring)
+		})
+		.collect()
+}
+
+/// Write the "Download edits" control, exporting the `[id, shape]` entries of the JS array `edits`.
+pub(crate) fn fmt_control(
+	f: &mut Formatter<'_>,
+	edits: &str,
+	position: ControlPosition,
+) -> fmt::Result {
+	f.write_str("(function () {\n")?;
+	f.write_str("\t\t\tconst position = ")?;
+	position.fmt_js(f)?;
+	f.write_str(";\n")?;
+
+	write!(
+		f,
+		r#"			const control = document.createElement("button");
+			control.textContent = "Download edits";
+			control.style.cssText = "margin: 10px; padding: 6px 10px; background: white; border: none; border-radius: 2px; box-shadow: rgba(0, 0, 0, 0.3) 0 1px 4px -1px; font: 13px Roboto, Arial, sans-serif; cursor: pointer";
+			const point = p => [p.lng(), p.lat()];
+			const ring = path => {{
+				const points = path.getArray().map(point);
+				if (points.length > 0) points.push(points[0]);
+				return points;
+			}};
+			function feature([id, shape]) {{
+				let type, geometry, radius;
+				if (shape instanceof google.maps.Marker) {{
+					type = "Marker";
+					geometry = {{ type: "Point", coordinates: point(shape.getPosition()) }};
+				}} else if (shape instanceof google.maps.Polyline) {{
+					type = "Polyline";
+					geometry = {{ type: "LineString", coordinates: shape.getPath().getArray().map(point) }};
+				}} else if (shape instanceof google.maps.Polygon) {{
+					type = "Polygon";
+					geometry = {{ type: "Polygon", coordinates: shape.getPaths().getArray().map(ring) }};
+				}} else if (shape instanceof google.maps.Rectangle) {{
+					type = "Rectangle";
+					const bounds = shape.getBounds(), ne = bounds.getNorthEast(), sw = bounds.getSouthWest();
+					const [n, e, s, w] = [ne.lat(), ne.lng(), sw.lat(), sw.lng()];
+					geometry = {{ type: "Polygon", coordinates: [[[w, s], [e, s], [e, n], [w, n], [w, s]]] }};
+				}} else if (shape instanceof google.maps.Circle) {{
+					type = "Circle";
+					geometry = {{ type: "Point", coordinates: point(shape.getCenter()) }};
+					radius = shape.getRadius();
+				}}
+				return {{ type: "Feature", id, properties: {{ id, shape: type, radius }}, geometry }};
+			}}
+			control.addEventListener("click", () => {{
+				const json = JSON.stringify({{ type: "FeatureCollection", features: {edits}.map(feature) }}, null, 2);
+				const link = document.createElement("a");
+				link.href = URL.createObjectURL(new Blob([json], {{ type: "application/geo+json" }}));
+				link.download = "edits.geojson";
+				link.click();
+				URL.revokeObjectURL(link.href);
+			}});
+			{map}.controls[position].push(control);
+		}})()"#,
+		map = MAP_IDENT.0,
+	)
+}
@@ -0,0 +1,174 @@
+use std::fmt::{self, Formatter};
+
+use crate::google::style::PolygonStyle;
+use crate::google::utils::JavaScript;
+use crate::google::MAP_IDENT;
+
+/// A user interaction with a shape, see e.g. [`Polygon::on`](crate::google::Polygon::on).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Event {
+	/// The shape was clicked.
+	Click,
+	/// The shape was double-clicked. Unless [`GoogleMap::disable_double_click_zoom`](crate::google::GoogleMap::disable_double_click_zoom)
+	/// is set, the map zooms in as well.
+	DoubleClick,
+	/// The mouse entered the shape.
+	MouseOver,
+	/// The mouse left the shape.
+	MouseOut,
+	/// The user stopped dragging the shape. Only draggable shapes can be dragged.
+	DragEnd,
+	/// The shape was right-clicked.
+	RightClick,
+}
+
+impl JavaScript for Event {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Event::Click => f.write_str("\"click\""),
+			Event::DoubleClick => f.write_str("\"dblclick\""),
+			Event::MouseOver => f.write_str("\"mouseover\""),
+			Event::MouseOut => f.write_str("\"mouseout\""),
+			Event::DragEnd => f.write_str("\"dragend\""),
+			Event::RightClick => f.write_str("\"rightclick\""),
+		}
+	}
+}
+
+/// What happens when an [`Event`] occurs on a shape.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::{Color, PolygonStyle};
+/// use mapplot::google::{Action, Event, GoogleMap, Marker, Polygon};
+///
+/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+///     .draw(
+///         Marker::new((51.507, -0.127))
+///             .on(Event::Click, Action::InfoWindow("<b>London</b>".to_string()))
+///             .on(Event::DoubleClick, Action::OpenUrl("https://en.wikipedia.org/wiki/London".to_string())),
+///     )
+///     .draw(
+///         Polygon::new([(51.4, -0.3), (51.6, -0.3), (51.6, 0.1)])
+///             .on(Event::MouseOver, Action::Highlight(PolygonStyle::new().fill_color(Color::Red)))
+///             .on(Event::Click, Action::ZoomTo)
+///             .on(Event::RightClick, Action::Script("console.log(event.latLng.toString());".to_string())),
+///     )
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Action {
+	/// Run a JavaScript snippet. Within the snippet, `this` is the shape, `event` is the Google Maps event (e.g. a
+	/// `google.maps.MapMouseEvent`) and `__map` is the map.
+	Script(String),
+	/// Open an info window with the given HTML content. The info window is anchored at a marker, or opened at the
+	/// position of the mouse for other shapes. Only one info window is open at a time.
+	InfoWindow(String),
+	/// Change the style of the shape. On [`Event::MouseOver`], the original style is restored when the mouse leaves
+	/// the shape, on other events the style is toggled. Has no effect on markers.
+	Highlight(PolygonStyle),
+	/// Zoom the map to fit the shape. The map is centered on markers without zooming.
+	ZoomTo,
+	/// Open the URL in a new browser tab.
+	OpenUrl(String),
+}
+
+impl Action {
+	/// Write the listener function of this action.
+	fn fmt_listener(&self, f: &mut Formatter<'_>, highlight: Option<bool>) -> fmt::Result {
+		f.write_str("function (event) { ")?;
+		match self {
+			Action::Script(script) => f.write_str(script)?,
+			Action::InfoWindow(content) => {
+				f.write_str("__info(this, event, ")?;
+				content.fmt_js(f)?;
+				f.write_str(");")?;
+			}
+			Action::Highlight(style) => {
+				f.write_str("__highlight(this, ")?;
+				style.fmt_js(f)?;
+				match highlight {
+					Some(on) => write!(f, ", {on});")?,
+					None => f.write_str(");")?,
+				}
+			}
+			Action::ZoomTo => f.write_str("__zoom_to(this);")?,
+			Action::OpenUrl(url) => {
+				f.write_str("window.open(")?;
+				url.fmt_js(f)?;
+				f.write_str(", \"_blank\");")?;
+			}
+		}
+		f.write_str(" }")
+	}
+}
+
+/// Write the helper functions used by the listeners.
+pub(crate) fn fmt_helpers(f: &mut Formatter<'_>) -> fmt::Result {
+	write!(
+		f,
+		r"		const __on = (shape, listeners) => (listeners.forEach(([event, listener]) => shape.addListener(event, listener)), shape);
+		let __info_window = null;
+		function __info(shape, event, content) {{
+			__info_window ??= new google.maps.InfoWindow();
+			__info_window.setContent(content);
+			if (shape instanceof google.maps.Marker) {{
+				__info_window.open({{ map: {map}, anchor: shape }});
+			}} else {{
+				__info_window.setPosition(event.latLng);
+				__info_window.open({{ map: {map} }});
+			}}
+		}}
+		function __highlight(shape, style, on = !shape.__style) {{
+			if (on && !shape.__style) {{
+				shape.__style = Object.fromEntries(Object.keys(style).map(key => [key, shape.get(key)]));
+				shape.setOptions(style);
+			}} else if (!on && shape.__style) {{
+				shape.setOptions(shape.__style);
+				shape.__style = null;
+			}}
+		}}
+		function __zoom_to(shape) {{
+			if (shape.getPosition) return {map}.panTo(shape.getPosition());
+			if (shape.getBounds) return {map}.fitBounds(shape.getBounds());
+			const bounds = new google.maps.LatLngBounds();
+			const paths = shape.getPaths ? shape.getPaths().getArray() : [shape.getPath()];
+			paths.forEach(path => path.forEach(point => bounds.extend(point)));
+			{map}.fitBounds(bounds);
+		}}
+",
+		map = MAP_IDENT.0,
+	)
+}
+
+/// Write the `[event, listener]` pairs of a shape as a JS array.
+pub(crate) fn fmt_listeners(f: &mut Formatter<'_>, events: &[(Event, Action)]) -> fmt::Result {
+	f.write_str("[")?;
+	let mut first = true;
+	for (event, action) in events {
+		// a highlight on mouseover is undone on mouseout
+		let listeners: &[(Event, Option<bool>)] = match (event, action) {
+			(Event::MouseOver, Action::Highlight(_)) => &[
+				(Event::MouseOver, Some(true)),
+				(Event::MouseOut, Some(false)),
+			],
+			_ => &[(*event, None)],
+		};
+		for (event, highlight) in listeners {
+			if !first {
+				f.write_str(", ")?;
+			}
+			first = false;
+			f.write_str("[")?;
+			event.fmt_js(f)?;
+			f.write_str(", ")?;
+			action.fmt_listener(f, *highlight)?;
+			f.write_str("]")?;
+		}
+	}
+	f.write_str("]")
+}
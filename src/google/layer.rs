@@ -0,0 +1,154 @@
+use std::fmt::{self, Formatter};
+
+use crate::google::utils::JavaScript;
+use crate::google::{ControlPosition, Shape, MAP_IDENT};
+
+/// A named group of shapes whose visibility can be toggled on the map.
+///
+/// Every layer added with [`GoogleMap::layer`](crate::google::GoogleMap::layer) gets a checkbox in the layer control.
+/// Base layers are mutually exclusive: exactly one of them is shown at a time, selected with radio buttons.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::Color;
+/// use mapplot::google::{Circle, GoogleMap, Layer, Marker, Polygon, Polyline};
+///
+/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+///     .layer(Layer::new("Depots").draw(Marker::new((51.507, -0.127))).draw(Marker::new((51.52, -0.08))))
+///     .layer(Layer::new("Routes").draw(Polyline::new([(51.507, -0.127), (51.52, -0.08)]).style(Color::Red)))
+///     .layer(Layer::new("Zones").visible(false).draw(Circle::new((51.5, -0.1), 5000.0)))
+///     .layer(Layer::new("Day").base(true).draw(Polygon::new([(51.4, -0.3), (51.6, -0.3), (51.6, 0.1)])))
+///     .layer(Layer::new("Night").base(true).draw(Polygon::new([(51.4, -0.3), (51.4, 0.1), (51.6, 0.1)])))
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Layer {
+	name: String,
+	visible: Option<bool>,
+	base: Option<bool>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, with = "crate::google::serde_shapes")
+	)]
+	pub(crate) shapes: Vec<Box<dyn Shape>>,
+}
+
+impl Layer {
+	/// Create a new empty layer, labeled with `name` in the layer control.
+	#[must_use]
+	pub fn new(name: impl AsRef<str>) -> Self {
+		Layer {
+			name: name.as_ref().to_string(),
+			visible: None,
+			base: None,
+			shapes: Vec::new(),
+		}
+	}
+
+	/// Whether the layer is initially visible. Defaults to `true`. Of multiple base layers, only the first visible
+	/// one is shown.
+	#[must_use]
+	pub fn visible(mut self, value: bool) -> Self {
+		self.visible = Some(value);
+		self
+	}
+
+	/// If set to `true`, the layer is a base layer, of which exactly one is shown at a time. Defaults to `false`.
+	#[must_use]
+	pub fn base(mut self, value: bool) -> Self {
+		self.base = Some(value);
+		self
+	}
+
+	/// Draw a shape on this layer.
+	///
+	/// All shapes of this crate are shown and hidden with the layer, a shape created hidden, e.g. with
+	/// [`Polyline::visible`](crate::google::Polyline::visible), stays hidden. A custom [`Shape`] is toggled if its
+	/// JavaScript evaluates to an object with a `setVisible` method, like the shapes of the Google Maps API, or to an
+	/// array of such objects.
+	#[must_use]
+	pub fn draw(mut self, shape: impl Shape + 'static) -> Self {
+		self.shapes.push(Box::new(shape));
+		self
+	}
+
+	/// Draw multiple shapes on this layer at once.
+	#[must_use]
+	pub fn draw_all(mut self, shapes: impl IntoIterator<Item = impl Shape + 'static>) -> Self {
+		for shape in shapes {
+			self.shapes.push(Box::new(shape));
+		}
+		self
+	}
+}
+
+/// Write the layer control, toggling the shapes in the JS array `shapes` which holds one array per layer.
+pub(crate) fn fmt_control(
+	f: &mut Formatter<'_>,
+	layers: &[Layer],
+	shapes: &str,
+	position: ControlPosition,
+) -> fmt::Result {
+	// only the first visible base layer is shown, or the first base layer if none is visible
+	let shown_base = layers
+		.iter()
+		.position(|l| l.base == Some(true) && l.visible != Some(false))
+		.or_else(|| layers.iter().position(|l| l.base == Some(true)));
+
+	f.write_str("(function () {\n")?;
+
+	// [name, base, visible]
+	f.write_str("\t\t\tconst layers = [")?;
+	for (i, layer) in layers.iter().enumerate() {
+		if i > 0 {
+			f.write_str(", ")?;
+		}
+		let base = layer.base == Some(true);
+		let visible = if base {
+			shown_base == Some(i)
+		} else {
+			layer.visible != Some(false)
+		};
+		f.write_str("[")?;
+		layer.name.fmt_js(f)?;
+		write!(f, ", {base}, {visible}]")?;
+	}
+	f.write_str("];\n")?;
+
+	f.write_str("\t\t\tconst position = ")?;
+	position.fmt_js(f)?;
+	f.write_str(";\n")?;
+
+	write!(
+		f,
+		r#"			const control = document.createElement("div");
+			control.style.cssText = "margin: 10px; padding: 6px 10px; background: white; border-radius: 2px; box-shadow: rgba(0, 0, 0, 0.3) 0 1px 4px -1px; font: 13px Roboto, Arial, sans-serif";
+			function item([name, base, visible], i) {{
+				const label = document.createElement("label");
+				label.style.cssText = "display: block; padding: 2px 0; cursor: pointer";
+				const input = document.createElement("input");
+				input.type = base ? "radio" : "checkbox";
+				if (base) input.name = "__base_layer";
+				input.checked = visible;
+				input.addEventListener("change", () => layers.forEach(([, b], j) => {{
+					if (base && b) __hide({shapes}[j], "layer", j !== i);
+					else if (j === i) __hide({shapes}[j], "layer", !input.checked);
+				}}));
+				label.append(input, " ", name);
+				__hide({shapes}[i], "layer", !visible);
+				return label;
+			}}
+			layers.forEach((layer, i) => layer[1] && control.append(item(layer, i)));
+			if (layers.some(([, base]) => base) && layers.some(([, base]) => !base)) {{
+				control.append(document.createElement("hr"));
+			}}
+			layers.forEach((layer, i) => !layer[1] && control.append(item(layer, i)));
+			{map}.controls[position].push(control);
+		}})()"#,
+		map = MAP_IDENT.0,
+	)
+}
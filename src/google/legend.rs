@@ -0,0 +1,216 @@
+use std::fmt::{self, Formatter, Write};
+
+use crate::google::style::{Color, ColorScale, PolylineStyle};
+use crate::google::utils::{escape_html, JavaScript};
+use crate::google::{ControlPosition, MAP_IDENT};
+
+/// A legend explaining the colors and symbols of the map.
+///
+/// A legend is a list of entries, each a color swatch, a marker or a line sample next to a label, and gradient bars
+/// for continuous color scales.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::{Color, PolylineStyle};
+/// use mapplot::google::{ControlPosition, GoogleMap, Legend};
+///
+/// let legend = Legend::new()
+///     .title("Deliveries")
+///     .marker("Depot")
+///     .line(PolylineStyle::new().color(Color::Red).width(3), "Route")
+///     .swatch(Color::RGBA(0, 0, 255, 128), "Delivery zone")
+///     .gradient([Color::Yellow, Color::Red], [(0.0, "0 min"), (0.5, "30 min"), (1.0, "60 min")])
+///     .position(ControlPosition::RightBottom);
+///
+/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+///     .legend(legend)
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Legend {
+	title: Option<String>,
+	entries: Vec<LegendEntry>,
+	position: Option<ControlPosition>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+enum LegendEntry {
+	Swatch(Color, String),
+	Marker(String),
+	Line(PolylineStyle, String),
+	Gradient(Vec<Color>, Vec<(f64, String)>),
+}
+
+impl Legend {
+	/// Create a new empty legend.
+	#[must_use]
+	pub fn new() -> Self {
+		Legend::default()
+	}
+
+	/// The title shown above the entries.
+	#[must_use]
+	pub fn title(mut self, value: impl AsRef<str>) -> Self {
+		self.title = Some(value.as_ref().to_string());
+		self
+	}
+
+	/// Add a square filled with `color`, e.g. for the fill of polygons.
+	#[must_use]
+	pub fn swatch(mut self, color: Color, label: impl AsRef<str>) -> Self {
+		self.entries
+			.push(LegendEntry::Swatch(color, label.as_ref().to_string()));
+		self
+	}
+
+	/// Add the default marker icon.
+	#[must_use]
+	pub fn marker(mut self, label: impl AsRef<str>) -> Self {
+		self.entries
+			.push(LegendEntry::Marker(label.as_ref().to_string()));
+		self
+	}
+
+	/// Add a line sample drawn with `style`, e.g. for polylines.
+	#[must_use]
+	pub fn line(mut self, style: impl Into<PolylineStyle>, label: impl AsRef<str>) -> Self {
+		self.entries
+			.push(LegendEntry::Line(style.into(), label.as_ref().to_string()));
+		self
+	}
+
+	/// Add a gradient bar through the evenly spaced `colors`, e.g. for a continuous color scale. The `ticks` are
+	/// labels at positions between `0.0` (the start of the bar) and `1.0` (the end of the bar).
+	#[must_use]
+	pub fn gradient(
+		mut self,
+		colors: impl IntoIterator<Item = Color>,
+		ticks: impl IntoIterator<Item = (f64, impl AsRef<str>)>,
+	) -> Self {
+		self.entries.push(LegendEntry::Gradient(
+			colors.into_iter().collect(),
+			ticks
+				.into_iter()
+				.map(|(pos, label)| (pos.clamp(0.0, 1.0), label.as_ref().to_string()))
+				.collect(),
+		));
+		self
+	}
+
+	/// Add a gradient bar for a [`ColorScale`], with tick labels at the given values.
+	#[must_use]
+	pub fn color_scale(self, scale: &ColorScale, ticks: impl IntoIterator<Item = f64>) -> Self {
+		let ticks: Vec<_> = ticks
+			.into_iter()
+			.map(|value| (scale.normalize(value), value.to_string()))
+			.collect();
+		self.gradient(scale.colormap().colors(16), ticks)
+	}
+
+	/// The position of the legend on the map. Defaults to [`ControlPosition::LeftBottom`].
+	#[must_use]
+	pub fn position(mut self, value: ControlPosition) -> Self {
+		self.position = Some(value);
+		self
+	}
+
+	/// The HTML content of the legend.
+	fn html(&self) -> String {
+		let mut html = String::new();
+		if let Some(title) = &self.title {
+			let _ = write!(
+				html,
+				r#"<div style="font-weight: 500; margin-bottom: 4px">{}</div>"#,
+				escape_html(title)
+			);
+		}
+		for entry in &self.entries {
+			html.push_str(
+				r#"<div style="display: flex; align-items: center; gap: 6px; margin: 2px 0">"#,
+			);
+			let label = match entry {
+				LegendEntry::Swatch(color, label) => {
+					let _ = write!(
+						html,
+						r#"<span style="width: 14px; height: 14px; background: {}; border: 1px solid rgba(0, 0, 0, 0.2)"></span>"#,
+						color.css()
+					);
+					label
+				}
+				LegendEntry::Marker(label) => {
+					html.push_str(
+						r##"<svg width="14" height="22" viewBox="0 0 14 22"><path d="M7 0C3.1 0 0 3.1 0 7c0 5.3 7 15 7 15s7-9.7 7-15c0-3.9-3.1-7-7-7z" fill="#ea4335" stroke="#a52714"/><circle cx="7" cy="7" r="2.5" fill="#3a0e07"/></svg>"##,
+					);
+					label
+				}
+				LegendEntry::Line(style, label) => {
+					let (color, opacity) = style.stroke();
+					let _ = write!(
+						html,
+						r#"<svg width="30" height="14"><line x1="0" y1="7" x2="30" y2="7" stroke="{}" stroke-opacity="{}" stroke-width="{}"/></svg>"#,
+						color.map_or("black".to_string(), Color::css),
+						opacity.unwrap_or(1.0),
+						style.stroke_weight.unwrap_or(3),
+					);
+					label
+				}
+				LegendEntry::Gradient(colors, ticks) => {
+					fmt_gradient(&mut html, colors, ticks);
+					continue;
+				}
+			};
+			let _ = write!(html, "<span>{}</span></div>", escape_html(label));
+		}
+		html
+	}
+
+	/// Write the legend as a control of the map.
+	pub(crate) fn fmt_control(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("(function () {\n")?;
+		f.write_str("\t\t\tconst control = document.createElement(\"div\");\n")?;
+		f.write_str("\t\t\tcontrol.style.cssText = \"margin: 10px; padding: 6px 10px; background: white; border-radius: 2px; box-shadow: rgba(0, 0, 0, 0.3) 0 1px 4px -1px; font: 13px Roboto, Arial, sans-serif\";\n")?;
+		f.write_str("\t\t\tcontrol.innerHTML = ")?;
+		self.html().fmt_js(f)?;
+		f.write_str(";\n")?;
+		write!(f, "\t\t\t{}.controls[", MAP_IDENT.0)?;
+		self.position
+			.unwrap_or(ControlPosition::LeftBottom)
+			.fmt_js(f)?;
+		f.write_str("].push(control);\n")?;
+		f.write_str("\t\t})()")
+	}
+}
+
+/// A gradient bar with tick labels below it, closing the entry.
+fn fmt_gradient(html: &mut String, colors: &[Color], ticks: &[(f64, String)]) {
+	let background = match colors {
+		[] => "transparent".to_string(),
+		[color] => color.css(),
+		colors => format!(
+			"linear-gradient(to right, {})",
+			colors
+				.iter()
+				.map(|c| c.css())
+				.collect::<Vec<_>>()
+				.join(", ")
+		),
+	};
+	let _ = write!(
+		html,
+		r#"<div style="width: 100%; min-width: 160px; padding: 0 12px 16px; box-sizing: border-box"><div style="height: 12px; background: {background}; border: 1px solid rgba(0, 0, 0, 0.2)"></div><div style="position: relative">"#
+	);
+	for (pos, label) in ticks {
+		let _ = write!(
+			html,
+			r#"<span style="position: absolute; left: {}%; transform: translateX(-50%); white-space: nowrap; font-size: 11px">{}</span>"#,
+			pos * 100.0,
+			escape_html(label)
+		);
+	}
+	html.push_str("</div></div></div>");
+}
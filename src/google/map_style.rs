@@ -0,0 +1,424 @@
+//! Styling of the base map, e.g. to dim it so that the drawn shapes stand out.
+//!
+//! See <https://developers.google.com/maps/documentation/javascript/style-reference>.
+//!
+//! # Examples
+//! ```
+//! use mapplot::google::map_style::{ElementType, FeatureType, MapStyle, StyleRule, Visibility};
+//! use mapplot::google::style::Color;
+//! use mapplot::google::GoogleMap;
+//!
+//! let style = MapStyle::grayscale()
+//!     .rule(StyleRule::new().feature(FeatureType::Poi).visibility(Visibility::Off))
+//!     .rule(
+//!         StyleRule::new()
+//!             .feature(FeatureType::Water)
+//!             .element(ElementType::Geometry)
+//!             .color(Color::Navy),
+//!     );
+//!
+//! let html = GoogleMap::new((0.0, 0.0), 1, "<your-apikey-here>")
+//!     .styles(style)
+//!     .to_string();
+//!
+//! std::fs::write("map.html", html).unwrap();
+//! ```
+
+use std::fmt::{self, Formatter};
+
+use crate::google::style::Color;
+use crate::google::utils::{FormatterExt, JavaScript};
+
+/// A set of rules that restyle the base map. Rules are applied in order, later rules override earlier ones.
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct MapStyle {
+	rules: Vec<StyleRule>,
+}
+
+impl MapStyle {
+	/// Create a new empty (default) map style.
+	#[must_use]
+	pub fn new() -> Self {
+		MapStyle::default()
+	}
+
+	/// Append a rule.
+	#[must_use]
+	pub fn rule(mut self, value: StyleRule) -> Self {
+		self.rules.push(value);
+		self
+	}
+
+	/// A dark theme, suitable for bright overlays.
+	#[must_use]
+	pub fn dark() -> Self {
+		let rule = |feature, element, color| {
+			StyleRule::new()
+				.feature(feature)
+				.element(element)
+				.color(color)
+		};
+
+		MapStyle::new()
+			.rule(rule(
+				FeatureType::All,
+				ElementType::Geometry,
+				Color::RGB(0x24, 0x2f, 0x3e),
+			))
+			.rule(rule(
+				FeatureType::All,
+				ElementType::LabelsTextStroke,
+				Color::RGB(0x24, 0x2f, 0x3e),
+			))
+			.rule(rule(
+				FeatureType::All,
+				ElementType::LabelsTextFill,
+				Color::RGB(0x74, 0x68, 0x55),
+			))
+			.rule(rule(
+				FeatureType::Poi,
+				ElementType::Geometry,
+				Color::RGB(0x26, 0x3c, 0x3f),
+			))
+			.rule(rule(
+				FeatureType::Road,
+				ElementType::Geometry,
+				Color::RGB(0x38, 0x41, 0x4e),
+			))
+			.rule(rule(
+				FeatureType::Road,
+				ElementType::GeometryStroke,
+				Color::RGB(0x21, 0x27, 0x2a),
+			))
+			.rule(rule(
+				FeatureType::RoadHighway,
+				ElementType::Geometry,
+				Color::RGB(0x74, 0x68, 0x55),
+			))
+			.rule(rule(
+				FeatureType::Transit,
+				ElementType::Geometry,
+				Color::RGB(0x2f, 0x39, 0x48),
+			))
+			.rule(rule(
+				FeatureType::Water,
+				ElementType::Geometry,
+				Color::RGB(0x17, 0x26, 0x3c),
+			))
+			.rule(rule(
+				FeatureType::Water,
+				ElementType::LabelsTextFill,
+				Color::RGB(0x51, 0x5c, 0x6d),
+			))
+	}
+
+	/// Remove all colors from the base map.
+	#[must_use]
+	pub fn grayscale() -> Self {
+		MapStyle::new().rule(StyleRule::new().saturation(-100))
+	}
+
+	/// A light, desaturated base map without points of interest and transit.
+	#[must_use]
+	pub fn minimal() -> Self {
+		let hide = |feature, element| {
+			StyleRule::new()
+				.feature(feature)
+				.element(element)
+				.visibility(Visibility::Off)
+		};
+
+		MapStyle::new()
+			.rule(StyleRule::new().saturation(-80).lightness(30))
+			.rule(hide(FeatureType::Poi, ElementType::All))
+			.rule(hide(FeatureType::Transit, ElementType::All))
+			.rule(hide(
+				FeatureType::AdministrativeLandParcel,
+				ElementType::All,
+			))
+			.rule(hide(FeatureType::Road, ElementType::LabelsIcon))
+	}
+}
+
+impl JavaScript for MapStyle {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		self.rules.fmt_js(f)
+	}
+}
+
+/// A style rule, i.e. a set of stylers applied to the selected features and elements.
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StyleRule {
+	feature_type: Option<FeatureType>,
+	element_type: Option<ElementType>,
+	stylers: Vec<Styler>,
+}
+
+impl StyleRule {
+	/// Create a new rule that selects all features and elements.
+	#[must_use]
+	pub fn new() -> Self {
+		StyleRule::default()
+	}
+
+	/// The feature, or group of features, to which the stylers apply. Defaults to [`FeatureType::All`].
+	#[must_use]
+	pub fn feature(mut self, value: FeatureType) -> Self {
+		self.feature_type = Some(value);
+		self
+	}
+
+	/// The element of the selected features to which the stylers apply. Defaults to [`ElementType::All`].
+	#[must_use]
+	pub fn element(mut self, value: ElementType) -> Self {
+		self.element_type = Some(value);
+		self
+	}
+
+	/// Whether and how the element appears on the map.
+	#[must_use]
+	pub fn visibility(mut self, value: Visibility) -> Self {
+		self.stylers.push(Styler::Visibility(value));
+		self
+	}
+
+	/// Set the color of the feature. Alpha is ignored.
+	#[must_use]
+	pub fn color(mut self, value: Color) -> Self {
+		self.stylers.push(Styler::Color(value));
+		self
+	}
+
+	/// Set the basic color of the feature, while keeping its lightness and saturation.
+	#[must_use]
+	pub fn hue(mut self, value: Color) -> Self {
+		self.stylers.push(Styler::Hue(value));
+		self
+	}
+
+	/// Percentage change in the brightness of the element, between -100 and 100.
+	#[must_use]
+	pub fn lightness(mut self, value: i8) -> Self {
+		self.stylers.push(Styler::Lightness(value));
+		self
+	}
+
+	/// Percentage change in the intensity of the basic color of the element, between -100 and 100.
+	#[must_use]
+	pub fn saturation(mut self, value: i8) -> Self {
+		self.stylers.push(Styler::Saturation(value));
+		self
+	}
+
+	/// The amount of gamma correction to apply to the element, between 0.01 and 10.0.
+	#[must_use]
+	pub fn gamma(mut self, value: f32) -> Self {
+		self.stylers.push(Styler::Gamma(value));
+		self
+	}
+
+	/// Invert the lightness of the element.
+	#[must_use]
+	pub fn invert_lightness(mut self, value: bool) -> Self {
+		self.stylers.push(Styler::InvertLightness(value));
+		self
+	}
+
+	/// The weight of the feature in pixels.
+	#[must_use]
+	pub fn weight(mut self, value: f32) -> Self {
+		self.stylers.push(Styler::Weight(value));
+		self
+	}
+}
+
+impl JavaScript for StyleRule {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_object()
+			.entry_opt("featureType", &self.feature_type)
+			.entry_opt("elementType", &self.element_type)
+			.entry("stylers", &self.stylers)
+			.finish()
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+enum Styler {
+	Visibility(Visibility),
+	Color(Color),
+	Hue(Color),
+	Lightness(i8),
+	Saturation(i8),
+	Gamma(f32),
+	InvertLightness(bool),
+	Weight(f32),
+}
+
+impl JavaScript for Styler {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let mut object = f.write_object();
+		match self {
+			Styler::Visibility(v) => object.entry("visibility", v),
+			Styler::Color(c) => object.entry("color", &HexColor(*c)),
+			Styler::Hue(c) => object.entry("hue", &HexColor(*c)),
+			Styler::Lightness(v) => object.entry("lightness", &isize::from(*v)),
+			Styler::Saturation(v) => object.entry("saturation", &isize::from(*v)),
+			Styler::Gamma(v) => object.entry("gamma", v),
+			Styler::InvertLightness(v) => object.entry("invert_lightness", v),
+			Styler::Weight(v) => object.entry("weight", v),
+		}
+		.finish()
+	}
+}
+
+/// Map styles only accept colors in the `#rrggbb` format.
+struct HexColor(Color);
+
+impl JavaScript for HexColor {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (r, g, b) = self.0.to_rgb();
+		write!(f, "\"#{r:02x}{g:02x}{b:02x}\"")
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Visibility {
+	/// The element is shown.
+	On,
+	/// The element is hidden.
+	Off,
+	/// The element is shown in a simplified way, e.g. without some labels.
+	Simplified,
+}
+
+impl JavaScript for Visibility {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Visibility::On => f.write_str("\"on\""),
+			Visibility::Off => f.write_str("\"off\""),
+			Visibility::Simplified => f.write_str("\"simplified\""),
+		}
+	}
+}
+
+macro_rules! selector_enum {
+	($(#[$meta:meta])* $name:ident { $($(#[doc = $doc:literal])* $variant:ident => $js:literal,)* }) => {
+		$(#[$meta])*
+		#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+		pub enum $name {
+			$($(#[doc = $doc])* $variant,)*
+		}
+
+		impl JavaScript for $name {
+			fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+				match self {
+					$($name::$variant => f.write_str(concat!("\"", $js, "\"")),)*
+				}
+			}
+		}
+	};
+}
+
+selector_enum! {
+	/// A feature, or group of features, on the base map.
+	FeatureType {
+		/// All features.
+		All => "all",
+		/// All administrative areas.
+		Administrative => "administrative",
+		/// Countries.
+		AdministrativeCountry => "administrative.country",
+		/// Land parcels.
+		AdministrativeLandParcel => "administrative.land_parcel",
+		/// Localities.
+		AdministrativeLocality => "administrative.locality",
+		/// Neighborhoods.
+		AdministrativeNeighborhood => "administrative.neighborhood",
+		/// Provinces.
+		AdministrativeProvince => "administrative.province",
+		/// All landscapes.
+		Landscape => "landscape",
+		/// Structures built by humans.
+		LandscapeManMade => "landscape.man_made",
+		/// Natural features.
+		LandscapeNatural => "landscape.natural",
+		/// Landcover features.
+		LandscapeNaturalLandcover => "landscape.natural.landcover",
+		/// Terrain features.
+		LandscapeNaturalTerrain => "landscape.natural.terrain",
+		/// All points of interest.
+		Poi => "poi",
+		/// Tourist attractions.
+		PoiAttraction => "poi.attraction",
+		/// Businesses.
+		PoiBusiness => "poi.business",
+		/// Government buildings.
+		PoiGovernment => "poi.government",
+		/// Emergency services, including hospitals, pharmacies, police, doctors, and others.
+		PoiMedical => "poi.medical",
+		/// Parks.
+		PoiPark => "poi.park",
+		/// Places of worship, such as churches, temples, or mosques.
+		PoiPlaceOfWorship => "poi.place_of_worship",
+		/// Schools.
+		PoiSchool => "poi.school",
+		/// Sports complexes.
+		PoiSportsComplex => "poi.sports_complex",
+		/// All roads.
+		Road => "road",
+		/// Arterial roads.
+		RoadArterial => "road.arterial",
+		/// Highways.
+		RoadHighway => "road.highway",
+		/// Highways with controlled access.
+		RoadHighwayControlledAccess => "road.highway.controlled_access",
+		/// Local roads.
+		RoadLocal => "road.local",
+		/// All transit stations and lines.
+		Transit => "transit",
+		/// Transit lines.
+		TransitLine => "transit.line",
+		/// All transit stations.
+		TransitStation => "transit.station",
+		/// Airports.
+		TransitStationAirport => "transit.station.airport",
+		/// Bus stops.
+		TransitStationBus => "transit.station.bus",
+		/// Rail stations.
+		TransitStationRail => "transit.station.rail",
+		/// Bodies of water.
+		Water => "water",
+	}
+}
+
+selector_enum! {
+	/// A part of a feature on the base map.
+	ElementType {
+		/// All elements of the feature.
+		All => "all",
+		/// All geometric elements of the feature.
+		Geometry => "geometry",
+		/// The fill of the geometry.
+		GeometryFill => "geometry.fill",
+		/// The stroke of the geometry.
+		GeometryStroke => "geometry.stroke",
+		/// The labels of the feature.
+		Labels => "labels",
+		/// The icons of the labels.
+		LabelsIcon => "labels.icon",
+		/// The texts of the labels.
+		LabelsText => "labels.text",
+		/// The fill of the label texts.
+		LabelsTextFill => "labels.text.fill",
+		/// The outline of the label texts.
+		LabelsTextStroke => "labels.text.stroke",
+	}
+}
@@ -1,24 +1,171 @@
+use std::collections::HashSet;
 use std::fmt::{self, Debug, Display, Formatter};
+use std::time::SystemTime;
 
-use crate::google::style::{PolygonStyle, PolylineStyle};
-use crate::google::utils::{FormatterExt, JavaScript, RawIdent};
+use crate::google::map_style::MapStyle;
+use crate::google::style::{Color, IconSequence, PolygonStyle, PolylineStyle};
+use crate::google::timeline::TimeRange;
+use crate::google::utils::{escape_html, escape_raw_text, FormatterExt, JavaScript, RawIdent};
+use crate::time::unix_millis;
 use crate::{BoundingBox, Location};
 
+pub use cluster::MarkerCluster;
+pub use drawing::{DrawingMode, DrawingTools};
+pub use event::{Action, Event};
+pub use layer::Layer;
+pub use legend::Legend;
+pub use timeline::TimeSlider;
+pub use track::AnimatedTrack;
+
+pub mod choropleth;
+mod cluster;
+mod drawing;
+pub mod edits;
+mod event;
+mod layer;
+mod legend;
+pub mod map_style;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_shapes;
+#[cfg(feature = "serve")]
+pub mod serve;
 pub mod style;
-mod utils;
+mod timeline;
+mod track;
+pub(crate) mod utils;
 
 const MAP_IDENT: RawIdent<'static> = RawIdent("__map");
 
+/// A map rendered as an HTML page using the Google Maps JavaScript API.
+///
+/// # Serialization
+/// With the `serde` feature enabled, the map and everything drawn on it can be serialized and deserialized. The
+/// JSON representation is an object with the following fields, where options that are not set are omitted:
+///
+/// - `apikey`, `page_title`: strings
+/// - `center`: a location, i.e. `{ "lat": 51.507, "lon": -0.127 }`
+/// - `zoom`: integer
+/// - `map_type`: one of `"Roadmap"`, `"Satellite"`, `"Hybrid"` or `"Terrain"`
+/// - `styles`: an array of style rules, each with optional `feature_type` and `element_type` fields (e.g.
+///   `"RoadHighway"`, `"LabelsText"`) and a `stylers` array, e.g. `[{ "visibility": "Off" }, { "lightness": 20 }]`
+/// - `map_id`: string
+/// - `min_zoom`, `max_zoom`: integers
+/// - `restriction`: `{ "bounds": { "p1": location, "p2": location }, "strict_bounds": boolean }`
+/// - `heading`, `tilt`: numbers
+/// - `background_color`: a color, see below
+/// - `clickable_pois`, `disable_default_gui`, `disable_double_click_zoom`, `fit_shapes`: booleans
+/// - `gesture_handling`: one of `"Cooperative"`, `"Greedy"`, `"None"` or `"Auto"`
+/// - `zoom_control`, `map_type_control`, `scale_control`, `street_view_control`, `fullscreen_control`: booleans
+/// - `zoom_control_position`, `map_type_control_position`, `street_view_control_position`,
+///   `fullscreen_control_position`: one of the [`ControlPosition`] variants, e.g. `"TopLeft"`
+/// - `time_slider`: `{ "step": duration, "range": boolean, "autoplay": boolean, "interval": duration, "position": ... }`
+///   with durations like `{ "secs": 3600, "nanos": 0 }`
+/// - `layer_control_position`, `edits_control_position`: one of the [`ControlPosition`] variants
+/// - `drawing_tools`: `{ "modes": ["Marker", "Polyline", ...], "polyline_style": style, "polygon_style": style,
+///   "position": ... }`
+/// - `head_html`, `css`, `scripts`, `html_before_map`, `html_after_map`: arrays of strings, see [`GoogleMap::script`]
+/// - `layers`: an array of `{ "name": string, "visible": boolean, "base": boolean, "shapes": [...] }`, the shapes
+///   being serialized like `shapes` below
+/// - `legends`: an array of `{ "title": string, "entries": [...], "position": ... }` with entries like
+///   `{ "Swatch": [color, label] }`, `{ "Marker": label }`, `{ "Line": [style, label] }` and
+///   `{ "Gradient": [[color, ...], [[position, label], ...]] }`
+/// - `shapes`: an array of shapes, each an object with a `type` field (`"Marker"`, `"Polyline"`, `"Polygon"`,
+///   `"Rectangle"`, `"Circle"`, `"MarkerCluster"`, `"AnimatedTrack"`, `"Choropleth"` or `"Geometry"`) and the options of the shape, named like the corresponding methods.
+///   A `"Geometry"` holds its shapes in a `geometry` field, e.g. `{ "MultiPoint": [{ "position": ... }] }`.
+///   Styles are nested in a `style` object; colors are either a named color like `"Red"` or one of
+///   `{ "RGB": [r, g, b] }`, `{ "RGBA": [r, g, b, a] }`, `{ "HSL": [h, s, l] }` and `{ "HSLA": [h, s, l, a] }`.
+///   Polyline symbols are listed in an `icons` array, e.g.
+///   `[{ "icon": { "path": "ForwardClosedArrow", "scale": 3.0 }, "repeat": { "Pixels": 20.0 } }]`.
+///   Times are objects like `{ "secs_since_epoch": 1625480430, "nanos_since_epoch": 0 }`. The `points` of an
+///   `"AnimatedTrack"` are `[time, location]` pairs, the `time` of other shapes is `{ "start": time, "end": time }`.
+///   Markers and editable shapes can have an `id` string. Event listeners are listed in an `events` array of `[event, action]` pairs, e.g.
+///   `[["Click", { "InfoWindow": "<b>London</b>" }], ["MouseOver", { "Highlight": style }], ["DoubleClick", "ZoomTo"]]`.
+///
+/// Only the shapes of this crate can be serialized, serializing a map with a custom [`Shape`] fails.
+///
+/// ```
+/// # #[cfg(feature = "serde")] {
+/// use mapplot::google::{style::Color, GoogleMap, Marker, Polyline};
+///
+/// let mut map = GoogleMap::new((51.5, -0.1), 7, None);
+/// map.draw(Marker::new((51.507, -0.127)).title("London"))
+///     .draw(Polyline::new([(51.507, -0.127), (52.48, -1.902)]).style(Color::Red));
+///
+/// let json = serde_json::to_string(&map).unwrap();
+/// assert_eq!(
+///     json,
+///     r#"{"apikey":"","center":{"lat":51.5,"lon":-0.1},"zoom":7,"shapes":[{"type":"Marker","position":{"lat":51.507,"lon":-0.127},"title":"London"},{"type":"Polyline","path":[{"lat":51.507,"lon":-0.127},{"lat":52.48,"lon":-1.902}],"style":{"stroke_color":"Red"}}]}"#
+/// );
+///
+/// let map: GoogleMap = serde_json::from_str(&json).unwrap();
+/// assert_eq!(serde_json::to_string(&map).unwrap(), json);
+/// # }
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GoogleMap {
 	apikey: String,
 	page_title: Option<String>,
 	center: Location,
 	zoom: u8,
+	min_zoom: Option<u8>,
+	max_zoom: Option<u8>,
+	restriction: Option<Restriction>,
+	heading: Option<f64>,
+	tilt: Option<f64>,
 	map_type: Option<MapType>,
+	styles: Option<MapStyle>,
+	map_id: Option<String>,
+	background_color: Option<Color>,
+	clickable_pois: Option<bool>,
+	gesture_handling: Option<GestureHandling>,
 	disable_default_gui: Option<bool>,
 	disable_double_click_zoom: Option<bool>,
+	fit_shapes: Option<bool>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	controls: Controls,
+	time_slider: Option<TimeSlider>,
+	layer_control_position: Option<ControlPosition>,
+	edits_control_position: Option<ControlPosition>,
+	drawing_tools: Option<DrawingTools>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	legends: Vec<Legend>,
+	#[cfg_attr(feature = "serde", serde(default, with = "serde_shapes"))]
 	shapes: Vec<Box<dyn Shape>>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	layers: Vec<Layer>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	head_html: Vec<String>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	css: Vec<String>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	scripts: Vec<String>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	html_before_map: Vec<String>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	html_after_map: Vec<String>,
 }
 
 impl GoogleMap {
@@ -33,10 +180,33 @@ impl GoogleMap {
 			page_title: None,
 			center: center.into(),
 			zoom,
+			min_zoom: None,
+			max_zoom: None,
+			restriction: None,
+			heading: None,
+			tilt: None,
 			map_type: None,
+			styles: None,
+			map_id: None,
+			background_color: None,
+			clickable_pois: None,
+			gesture_handling: None,
 			disable_default_gui: None,
 			disable_double_click_zoom: None,
+			fit_shapes: None,
+			controls: Controls::default(),
+			time_slider: None,
+			layer_control_position: None,
+			edits_control_position: None,
+			drawing_tools: None,
+			legends: Vec::default(),
 			shapes: Vec::default(),
+			layers: Vec::default(),
+			head_html: Vec::default(),
+			css: Vec::default(),
+			scripts: Vec::default(),
+			html_before_map: Vec::default(),
+			html_after_map: Vec::default(),
 		}
 	}
 
@@ -46,12 +216,189 @@ impl GoogleMap {
 		self
 	}
 
+	/// Append HTML to the `<head>` of the page, e.g. `<link>` or `<meta>` elements. The HTML is inserted as is.
+	pub fn head_html(&mut self, html: impl AsRef<str>) -> &mut Self {
+		self.head_html.push(html.as_ref().to_string());
+		self
+	}
+
+	/// Append a style sheet to the page. Any `</` is written as `<\/` so the CSS can't end its `<style>` element.
+	///
+	/// The map is in a `div` with the ID `map_canvas`, sized by its `style` attribute; use `!important` to override it.
+	pub fn css(&mut self, css: impl AsRef<str>) -> &mut Self {
+		self.css.push(css.as_ref().to_string());
+		self
+	}
+
+	/// Append a script run after the map and everything drawn on it is initialized. The map is available as `__map`,
+	/// the shapes with an ID as described in [`Marker::id`]. Each script runs in its own block, and any `</` is written
+	/// as `<\/` so the script can't end its `<script>` element.
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::google::{GoogleMap, Marker};
+	///
+	/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+	///     .draw(Marker::new((51.507, -0.127)).id("london"))
+	///     .script(r#"__map.panTo($london.getPosition()); console.log("</script>");"#)
+	///     .to_string();
+	///
+	/// assert!(html.contains(r#"console.log("<\/script>");"#));
+	/// ```
+	pub fn script(&mut self, script: impl AsRef<str>) -> &mut Self {
+		self.scripts.push(script.as_ref().to_string());
+		self
+	}
+
+	/// Append HTML to the `<body>` of the page, before the map. The HTML is inserted as is.
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::google::GoogleMap;
+	///
+	/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+	///     .css("#map_canvas { height: calc(100% - 40px) !important; } header { height: 40px; }")
+	///     .html_before_map("<header>Bus stops in London</header>")
+	///     .to_string();
+	///
+	/// assert!(html.contains("<header>Bus stops in London</header>\n\t<div id=\"map_canvas\""));
+	/// ```
+	pub fn html_before_map(&mut self, html: impl AsRef<str>) -> &mut Self {
+		self.html_before_map.push(html.as_ref().to_string());
+		self
+	}
+
+	/// Append HTML to the `<body>` of the page, after the map. The HTML is inserted as is.
+	pub fn html_after_map(&mut self, html: impl AsRef<str>) -> &mut Self {
+		self.html_after_map.push(html.as_ref().to_string());
+		self
+	}
+
+	/// The minimum zoom level which will be displayed on the map.
+	pub fn min_zoom(&mut self, value: u8) -> &mut Self {
+		self.min_zoom = Some(value);
+		self
+	}
+
+	/// The maximum zoom level which will be displayed on the map.
+	pub fn max_zoom(&mut self, value: u8) -> &mut Self {
+		self.max_zoom = Some(value);
+		self
+	}
+
+	/// Restrict the area of the map accessible to users. When `strict_bounds` is `true`, the user can't zoom out
+	/// further than the bounds allow, otherwise only the center of the map is kept within the bounds.
+	pub fn restriction(&mut self, bounds: BoundingBox, strict_bounds: bool) -> &mut Self {
+		self.restriction = Some(Restriction {
+			bounds,
+			strict_bounds,
+		});
+		self
+	}
+
+	/// The heading of aerial imagery in degrees measured clockwise from cardinal direction North.
+	pub fn heading(&mut self, value: f64) -> &mut Self {
+		self.heading = Some(value);
+		self
+	}
+
+	/// The angle of incidence of the map in degrees from the viewport plane to the map plane. Only `0` and `45` are
+	/// supported for satellite and hybrid maps, and only at high zoom levels.
+	pub fn tilt(&mut self, value: f64) -> &mut Self {
+		self.tilt = Some(value);
+		self
+	}
+
 	/// The initial map type. Defaults to [`MapType::Roadmap`].
 	pub fn map_type(&mut self, value: MapType) -> &mut Self {
 		self.map_type = Some(value);
 		self
 	}
 
+	/// Restyle the base map, see [`map_style`]. Ignored if a [`map_id`](GoogleMap::map_id) is set.
+	pub fn styles(&mut self, value: MapStyle) -> &mut Self {
+		self.styles = Some(value);
+		self
+	}
+
+	/// The ID of a map style created in the Google Cloud Console. Takes precedence over [`styles`](GoogleMap::styles).
+	pub fn map_id(&mut self, value: impl AsRef<str>) -> &mut Self {
+		self.map_id = Some(value.as_ref().to_string());
+		self
+	}
+
+	/// The color of the map background, visible while tiles are loading.
+	pub fn background_color(&mut self, value: Color) -> &mut Self {
+		self.background_color = Some(value);
+		self
+	}
+
+	/// Whether points of interest are clickable. Enabled by default.
+	pub fn clickable_pois(&mut self, value: bool) -> &mut Self {
+		self.clickable_pois = Some(value);
+		self
+	}
+
+	/// How the map handles gestures such as scrolling and pinching. Defaults to [`GestureHandling::Auto`].
+	pub fn gesture_handling(&mut self, value: GestureHandling) -> &mut Self {
+		self.gesture_handling = Some(value);
+		self
+	}
+
+	/// Enable/disable the zoom control.
+	pub fn zoom_control(&mut self, value: bool) -> &mut Self {
+		self.controls.zoom = Some(value);
+		self
+	}
+
+	/// The position of the zoom control.
+	pub fn zoom_control_position(&mut self, value: ControlPosition) -> &mut Self {
+		self.controls.zoom_position = Some(value);
+		self
+	}
+
+	/// Enable/disable the map type control, i.e. the switch between e.g. [`MapType::Roadmap`] and [`MapType::Satellite`].
+	pub fn map_type_control(&mut self, value: bool) -> &mut Self {
+		self.controls.map_type = Some(value);
+		self
+	}
+
+	/// The position of the map type control.
+	pub fn map_type_control_position(&mut self, value: ControlPosition) -> &mut Self {
+		self.controls.map_type_position = Some(value);
+		self
+	}
+
+	/// Enable/disable the scale control. Disabled by default.
+	pub fn scale_control(&mut self, value: bool) -> &mut Self {
+		self.controls.scale = Some(value);
+		self
+	}
+
+	/// Enable/disable the Street View Pegman control.
+	pub fn street_view_control(&mut self, value: bool) -> &mut Self {
+		self.controls.street_view = Some(value);
+		self
+	}
+
+	/// The position of the Street View Pegman control.
+	pub fn street_view_control_position(&mut self, value: ControlPosition) -> &mut Self {
+		self.controls.street_view_position = Some(value);
+		self
+	}
+
+	/// Enable/disable the fullscreen control.
+	pub fn fullscreen_control(&mut self, value: bool) -> &mut Self {
+		self.controls.fullscreen = Some(value);
+		self
+	}
+
+	/// The position of the fullscreen control.
+	pub fn fullscreen_control_position(&mut self, value: ControlPosition) -> &mut Self {
+		self.controls.fullscreen_position = Some(value);
+		self
+	}
+
 	/// Enable/disable all default UI buttons.
 	pub fn disable_default_gui(&mut self, value: bool) -> &mut Self {
 		self.disable_default_gui = Some(value);
@@ -64,6 +411,32 @@ impl GoogleMap {
 		self
 	}
 
+	/// Center and zoom the map to show all shapes, including the ones in layers, instead of using the center and zoom
+	/// given to [`GoogleMap::new`]. A map with a single marker is only centered on it.
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::google::{GoogleMap, Marker, Polyline};
+	///
+	/// let html = GoogleMap::new((0.0, 0.0), 1, "<your-apikey-here>")
+	///     .draw(Marker::new((51.507, -0.127)))
+	///     .draw(Polyline::new([(48.856, 2.352), (52.52, 13.405)]))
+	///     .fit_shapes(true)
+	///     .to_string();
+	///
+	/// assert!(html.contains("fitBounds("));
+	/// ```
+	pub fn fit_shapes(&mut self, value: bool) -> &mut Self {
+		self.fit_shapes = Some(value);
+		self
+	}
+
+	/// Add a [`TimeSlider`] to the map, showing only the shapes whose time lies within the selected time.
+	pub fn time_slider(&mut self, value: TimeSlider) -> &mut Self {
+		self.time_slider = Some(value);
+		self
+	}
+
 	/// Draw a shape on the map.
 	pub fn draw(&mut self, shape: impl Shape + 'static) -> &mut Self {
 		self.shapes.push(Box::new(shape));
@@ -80,34 +453,385 @@ impl GoogleMap {
 		}
 		self
 	}
+
+	/// Add a layer to the map. The map shows a control to toggle the visibility of the layers.
+	pub fn layer(&mut self, layer: Layer) -> &mut Self {
+		self.layers.push(layer);
+		self
+	}
+
+	/// The position of the layer control. Defaults to [`ControlPosition::TopRight`].
+	pub fn layer_control_position(&mut self, value: ControlPosition) -> &mut Self {
+		self.layer_control_position = Some(value);
+		self
+	}
+
+	/// The position of the "Download edits" button, which is shown if any editable or draggable shape has an ID (see
+	/// [`edits`]) or if the map has [`DrawingTools`]. Defaults to [`ControlPosition::TopCenter`].
+	pub fn edits_control_position(&mut self, value: ControlPosition) -> &mut Self {
+		self.edits_control_position = Some(value);
+		self
+	}
+
+	/// Show a toolbar for drawing new shapes, which can be downloaded with the "Download edits" button.
+	pub fn drawing_tools(&mut self, value: DrawingTools) -> &mut Self {
+		self.drawing_tools = Some(value);
+		self
+	}
+
+	/// Add a legend to the map. Can be called multiple times.
+	pub fn legend(&mut self, legend: Legend) -> &mut Self {
+		self.legends.push(legend);
+		self
+	}
 }
 
 impl JavaScript for GoogleMap {
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		self.fmt_map(f, None)
+	}
+}
+
+impl GoogleMap {
+	/// Write the map with everything drawn on it, followed by the `live_script` of a served page.
+	fn fmt_map(&self, f: &mut Formatter<'_>, live_script: Option<&str>) -> fmt::Result {
 		f.write_str("\t\tconst ")?;
 		MAP_IDENT.fmt_js(f)?;
 		f.write_str(" = new google.maps.Map(document.getElementById(\"map_canvas\"), ")?;
 		f.write_object()
 			.entry("center", &self.center)
 			.entry("zoom", &self.zoom)
+			.entry_opt("minZoom", &self.min_zoom)
+			.entry_opt("maxZoom", &self.max_zoom)
+			.entry_opt("restriction", &self.restriction)
+			.entry_opt("heading", &self.heading)
+			.entry_opt("tilt", &self.tilt)
 			.entry_opt("mapTypeId", &self.map_type)
+			.entry_opt("styles", &self.styles)
+			.entry_opt("mapId", &self.map_id)
+			.entry_opt("backgroundColor", &self.background_color)
+			.entry_opt("clickableIcons", &self.clickable_pois)
+			.entry_opt("gestureHandling", &self.gesture_handling)
 			.entry_opt("disableDefaultUI", &self.disable_default_gui)
 			.entry_opt("disableDoubleClickZoom", &self.disable_double_click_zoom)
+			.entry_opt("zoomControl", &self.controls.zoom)
+			.entry_opt(
+				"zoomControlOptions",
+				&self.controls.zoom_position.map(ControlOptions),
+			)
+			.entry_opt("mapTypeControl", &self.controls.map_type)
+			.entry_opt(
+				"mapTypeControlOptions",
+				&self.controls.map_type_position.map(ControlOptions),
+			)
+			.entry_opt("scaleControl", &self.controls.scale)
+			.entry_opt("streetViewControl", &self.controls.street_view)
+			.entry_opt(
+				"streetViewControlOptions",
+				&self.controls.street_view_position.map(ControlOptions),
+			)
+			.entry_opt("fullscreenControl", &self.controls.fullscreen)
+			.entry_opt(
+				"fullscreenControlOptions",
+				&self.controls.fullscreen_position.map(ControlOptions),
+			)
 			.finish()?;
 		f.write_str(");\n\n")?;
 
+		self.fmt_shapes(f, live_script.is_some())?;
+		if self.fit_shapes == Some(true) {
+			self.fmt_fit(f)?;
+		}
+
+		for legend in &self.legends {
+			f.write_str("\t\t")?;
+			legend.fmt_control(f)?;
+			f.write_str(";\n")?;
+		}
+
+		if let Some(script) = live_script {
+			f.write_str(script)?;
+		}
+
+		for script in &self.scripts {
+			write!(f, "\t\t{{\n{}\n\t\t}}\n", escape_raw_text(script))?;
+		}
+
+		Ok(())
+	}
+
+	/// Write the shapes and layers as well as the controls filtering them. A `live` map always has the helpers of the
+	/// event listeners and the `__shapes` lookup, for the shapes added later.
+	fn fmt_shapes(&self, f: &mut Formatter<'_>, live: bool) -> fmt::Result {
+		let all_shapes = || {
+			self.shapes
+				.iter()
+				.chain(self.layers.iter().flat_map(|l| &l.shapes))
+		};
+
+		// the time span of all shapes, if they are filtered by a time slider
+		let time_bounds = self.time_slider.and_then(|_| {
+			all_shapes()
+				.filter_map(Shape::time_range)
+				.reduce(|(s1, e1), (s2, e2)| (s1.min(s2), e1.max(e2)))
+		});
+		let timed = time_bounds.is_some();
+		let edited = self.drawing_tools.is_some()
+			|| all_shapes().any(|s| s.is_editable() && s.shape_id().is_some());
+		// the shapes drawn as part of others have their own listeners and IDs
+		let parts = || all_shapes().flat_map(|s| s.parts());
+		let listened = live
+			|| all_shapes().any(|s| !s.events().is_empty())
+			|| parts().any(|s| !s.events().is_empty());
+		let named = live
+			|| all_shapes().any(|s| s.shape_id().is_some())
+			|| parts().any(|s| s.shape_id().is_some());
+
+		self.fmt_helpers(f, timed, edited, listened)?;
+		if named {
+			// the shapes by their IDs, also reachable from outside the page as `window.__shapes`
+			f.write_str(
+				"\t\tconst __shapes = window.__shapes = {};\n\
+				\t\tconst __id = (id, shape) => (__shapes[id] ??= shape, shape);\n",
+			)?;
+		}
+
+		// the variables declared so far, a later shape with the same variable name is only in `__shapes`
+		let mut variables = HashSet::new();
+		let mut declare = |shape: &dyn Shape| {
+			shape
+				.shape_id()
+				.map(shape_variable)
+				.filter(|v| variables.insert(v.clone()))
+		};
+
 		for shape in &self.shapes {
 			f.write_str("\t\t")?;
-			shape.fmt_js(f)?;
+			if let Some(variable) = declare(shape) {
+				write!(f, "const {variable} = ")?;
+			}
+			fmt_shape(f, shape, timed)?;
+			f.write_str(";\n")?;
+		}
+
+		if !self.layers.is_empty() {
+			f.write_str("\t\tconst __layers = [];\n")?;
+			for layer in &self.layers {
+				// named shapes are declared before the layer, which refers to them by their variables
+				let mut shapes = Vec::new();
+				for shape in &layer.shapes {
+					let variable = declare(shape);
+					if let Some(variable) = &variable {
+						write!(f, "\t\tconst {variable} = ")?;
+						fmt_shape(f, shape, timed)?;
+						f.write_str(";\n")?;
+					}
+					shapes.push((shape, variable));
+				}
+
+				f.write_str("\t\t__layers.push([")?;
+				for (i, (shape, variable)) in shapes.into_iter().enumerate() {
+					if i > 0 {
+						f.write_str(", ")?;
+					}
+					match variable {
+						Some(variable) => f.write_str(&variable)?,
+						None => fmt_shape(f, shape, timed)?,
+					}
+				}
+				f.write_str("]);\n")?;
+			}
+			f.write_str("\t\t")?;
+			layer::fmt_control(
+				f,
+				&self.layers,
+				"__layers",
+				self.layer_control_position
+					.unwrap_or(ControlPosition::TopRight),
+			)?;
 			f.write_str(";\n")?;
 		}
 
+		if let (Some(slider), Some(bounds)) = (&self.time_slider, time_bounds) {
+			f.write_str("\t\t")?;
+			slider.fmt_control(f, "__timed", bounds)?;
+			f.write_str(";\n")?;
+		}
+
+		if let Some(tools) = &self.drawing_tools {
+			f.write_str("\t\t")?;
+			tools.fmt_control(f, "__edits")?;
+			f.write_str(";\n")?;
+		}
+
+		if edited {
+			f.write_str("\t\t")?;
+			edits::fmt_control(
+				f,
+				"__edits",
+				self.edits_control_position
+					.unwrap_or(ControlPosition::TopCenter),
+			)?;
+			f.write_str(";\n")?;
+		}
+
+		Ok(())
+	}
+
+	/// Center and zoom the map to show all shapes.
+	fn fmt_fit(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let bounds = self
+			.shapes
+			.iter()
+			.chain(self.layers.iter().flat_map(|l| &l.shapes))
+			.filter_map(Shape::bounds)
+			.reduce(BoundingBox::union);
+		match bounds {
+			Some(b) if (b.p1.lat, b.p1.lon) == (b.p2.lat, b.p2.lon) => {
+				f.write_str("\t\t")?;
+				MAP_IDENT.fmt_js(f)?;
+				f.write_str(".setCenter(")?;
+				b.p1.fmt_js(f)?;
+				f.write_str(");\n")
+			}
+			Some(b) => {
+				f.write_str("\t\t")?;
+				MAP_IDENT.fmt_js(f)?;
+				f.write_str(".fitBounds(")?;
+				b.fmt_js(f)?;
+				f.write_str(");\n")
+			}
+			None => Ok(()),
+		}
+	}
+
+	/// Write the JS helpers used by the shapes and controls.
+	fn fmt_helpers(
+		&self,
+		f: &mut Formatter<'_>,
+		timed: bool,
+		edited: bool,
+		listened: bool,
+	) -> fmt::Result {
+		if listened {
+			event::fmt_helpers(f)?;
+		}
+		if timed || !self.layers.is_empty() {
+			// a shape is visible unless hidden for any reason, e.g. by the time slider and by the layer control; a shape
+			// created hidden stays hidden for good
+			f.write_str(
+				"\t\tconst __hide = (shape, reason, hidden) => Array.isArray(shape)\n\
+				\t\t\t? shape.forEach(s => __hide(s, reason, hidden))\n\
+				\t\t\t: shape && shape.setVisible && (\
+				shape.__hidden ??= new Set(shape.getVisible && shape.getVisible() === false ? [\"created\"] : []), \
+				hidden ? shape.__hidden.add(reason) : shape.__hidden.delete(reason), \
+				shape.setVisible(shape.__hidden.size === 0));\n",
+			)?;
+		}
+		if timed {
+			f.write_str(
+				"\t\tconst __timed = [];\n\
+				\t\tconst __time = (start, end, shape) => (__timed.push([start, end, shape]), shape);\n",
+			)?;
+		}
+		if edited {
+			f.write_str(
+				"\t\tconst __edits = [];\n\
+				\t\tconst __edit = (id, shape) => (__edits.push([id, shape]), shape);\n",
+			)?;
+		}
 		Ok(())
 	}
 }
 
+/// Write a shape with its event listeners, registering it in `__shapes` if it has an ID, in `__timed` if the shapes are
+/// filtered by a time slider and in `__edits` if it's editable and has an ID.
+fn fmt_shape(f: &mut Formatter<'_>, shape: &dyn Shape, timed: bool) -> fmt::Result {
+	let id = shape.shape_id();
+	if let Some(id) = id {
+		f.write_str("__id(")?;
+		id.fmt_js(f)?;
+		f.write_str(", ")?;
+	}
+	let time_range = shape.time_range().filter(|_| timed);
+	if let Some((start, end)) = time_range {
+		write!(f, "__time({}, {}, ", unix_millis(start), unix_millis(end))?;
+	}
+	let edited_id = id.filter(|_| shape.is_editable());
+	if let Some(id) = edited_id {
+		f.write_str("__edit(")?;
+		id.fmt_js(f)?;
+		f.write_str(", ")?;
+	}
+
+	fmt_listened_shape(f, shape)?;
+
+	if edited_id.is_some() {
+		f.write_str(")")?;
+	}
+	if time_range.is_some() {
+		f.write_str(")")?;
+	}
+	if id.is_some() {
+		f.write_str(")")?;
+	}
+	Ok(())
+}
+
+/// The JS variable of a shape with an ID: `$` followed by the ID, with all characters other than ASCII letters, digits,
+/// `_` and `$` replaced by `_`. The prefix keeps IDs like `google` from shadowing the globals used by the page.
+fn shape_variable(id: &str) -> String {
+	std::iter::once('$')
+		.chain(id.chars().map(|c| {
+			if c.is_ascii_alphanumeric() || c == '_' || c == '$' {
+				c
+			} else {
+				'_'
+			}
+		}))
+		.collect()
+}
+
+/// Write a shape drawn as part of another shape, e.g. a region of a [`Choropleth`](choropleth::Choropleth), with its
+/// event listeners, registering it in `__shapes` if it has an ID.
+pub(crate) fn fmt_part(f: &mut Formatter<'_>, shape: &dyn Shape) -> fmt::Result {
+	let id = shape.shape_id();
+	if let Some(id) = id {
+		f.write_str("__id(")?;
+		id.fmt_js(f)?;
+		f.write_str(", ")?;
+	}
+	fmt_listened_shape(f, shape)?;
+	if id.is_some() {
+		f.write_str(")")?;
+	}
+	Ok(())
+}
+
+/// Write a shape, adding its event listeners with `__on`.
+pub(crate) fn fmt_listened_shape(f: &mut Formatter<'_>, shape: &dyn Shape) -> fmt::Result {
+	let events = shape.events();
+	if events.is_empty() {
+		shape.fmt_js(f)
+	} else {
+		f.write_str("__on(")?;
+		shape.fmt_js(f)?;
+		f.write_str(", ")?;
+		event::fmt_listeners(f, events)?;
+		f.write_str(")")
+	}
+}
+
 impl Display for GoogleMap {
 	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		self.fmt_page(f, None)
+	}
+}
+
+impl GoogleMap {
+	/// Write the HTML page. The `live_script` runs at the end of the initialization, with the map and the helpers of
+	/// the event listeners in scope.
+	pub(crate) fn fmt_page(&self, f: &mut Formatter<'_>, live_script: Option<&str>) -> fmt::Result {
 		write!(
 			f,
 			r#"
@@ -117,35 +841,46 @@ impl Display for GoogleMap {
 <meta http-equiv="content-type" content="text/html; charset=UTF-8"/>
 <title>{title}</title>
 <script type="text/javascript" src="https://maps.googleapis.com/maps/api/js?libraries=visualization&sensor=true_or_false&key={apikey}"></script>
-<script type="text/javascript">
-	function initialize() {{
 "#,
-			title = if let Some(t) = &self.page_title {
-				t.as_str()
-			} else {
-				"Google Maps - mapplot"
-			},
-			apikey = self.apikey
+			title = escape_html(
+				self.page_title
+					.as_deref()
+					.unwrap_or("Google Maps - mapplot")
+			),
+			apikey = escape_html(&self.apikey)
 		)?;
 
-		self.fmt_js(f)?;
+		for html in &self.head_html {
+			writeln!(f, "{html}")?;
+		}
+		for css in &self.css {
+			writeln!(
+				f,
+				"<style type=\"text/css\">\n{}\n</style>",
+				escape_raw_text(css)
+			)?;
+		}
+
+		f.write_str("<script type=\"text/javascript\">\n\tfunction initialize() {\n")?;
+		self.fmt_map(f, live_script)?;
+		f.write_str(
+			"\n\t}\n</script>\n</head>\n<body style=\"margin:0px; padding:0px;\" onload=\"initialize()\">\n",
+		)?;
 
-		write!(
-			f,
-			r#"
-	}}
-</script>
-</head>
-<body style="margin:0px; padding:0px;" onload="initialize()">
-	<div id="map_canvas" style="width: 100%; height: 100%;"></div>
-</body>
-</html>
-"#
-		)
+		for html in &self.html_before_map {
+			writeln!(f, "{html}")?;
+		}
+		f.write_str("\t<div id=\"map_canvas\" style=\"width: 100%; height: 100%;\"></div>\n")?;
+		for html in &self.html_after_map {
+			writeln!(f, "{html}")?;
+		}
+
+		f.write_str("</body>\n</html>\n")
 	}
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MapType {
 	/// A normal street map.
 	Roadmap,
@@ -168,22 +903,235 @@ impl JavaScript for MapType {
 	}
 }
 
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GestureHandling {
+	/// Scroll events and one-finger touch gestures scroll the page, and do not zoom or pan the map.
+	Cooperative,
+	/// All touch gestures and scroll events pan or zoom the map.
+	Greedy,
+	/// The map cannot be panned or zoomed by user gestures.
+	None,
+	/// Cooperative if the map is in an iframe, greedy otherwise.
+	Auto,
+}
+
+impl JavaScript for GestureHandling {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			GestureHandling::Cooperative => f.write_str("\"cooperative\""),
+			GestureHandling::Greedy => f.write_str("\"greedy\""),
+			GestureHandling::None => f.write_str("\"none\""),
+			GestureHandling::Auto => f.write_str("\"auto\""),
+		}
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ControlPosition {
+	/// Elements are positioned in the top left and flow towards the middle.
+	TopLeft,
+	/// Elements are positioned in the center of the top row.
+	TopCenter,
+	/// Elements are positioned in the top right and flow towards the middle.
+	TopRight,
+	/// Elements are positioned on the left, below top-left elements, and flow downwards.
+	LeftTop,
+	/// Elements are positioned in the center of the left side.
+	LeftCenter,
+	/// Elements are positioned on the left, above bottom-left elements, and flow upwards.
+	LeftBottom,
+	/// Elements are positioned on the right, below top-right elements, and flow downwards.
+	RightTop,
+	/// Elements are positioned in the center of the right side.
+	RightCenter,
+	/// Elements are positioned on the right, above bottom-right elements, and flow upwards.
+	RightBottom,
+	/// Elements are positioned in the bottom left and flow towards the middle.
+	BottomLeft,
+	/// Elements are positioned in the center of the bottom row.
+	BottomCenter,
+	/// Elements are positioned in the bottom right and flow towards the middle.
+	BottomRight,
+}
+
+impl JavaScript for ControlPosition {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ControlPosition::TopLeft => f.write_str("google.maps.ControlPosition.TOP_LEFT"),
+			ControlPosition::TopCenter => f.write_str("google.maps.ControlPosition.TOP_CENTER"),
+			ControlPosition::TopRight => f.write_str("google.maps.ControlPosition.TOP_RIGHT"),
+			ControlPosition::LeftTop => f.write_str("google.maps.ControlPosition.LEFT_TOP"),
+			ControlPosition::LeftCenter => f.write_str("google.maps.ControlPosition.LEFT_CENTER"),
+			ControlPosition::LeftBottom => f.write_str("google.maps.ControlPosition.LEFT_BOTTOM"),
+			ControlPosition::RightTop => f.write_str("google.maps.ControlPosition.RIGHT_TOP"),
+			ControlPosition::RightCenter => f.write_str("google.maps.ControlPosition.RIGHT_CENTER"),
+			ControlPosition::RightBottom => f.write_str("google.maps.ControlPosition.RIGHT_BOTTOM"),
+			ControlPosition::BottomLeft => f.write_str("google.maps.ControlPosition.BOTTOM_LEFT"),
+			ControlPosition::BottomCenter => {
+				f.write_str("google.maps.ControlPosition.BOTTOM_CENTER")
+			}
+			ControlPosition::BottomRight => f.write_str("google.maps.ControlPosition.BOTTOM_RIGHT"),
+		}
+	}
+}
+
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Default, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Controls {
+	#[cfg_attr(feature = "serde", serde(rename = "zoom_control"))]
+	zoom: Option<bool>,
+	#[cfg_attr(feature = "serde", serde(rename = "zoom_control_position"))]
+	zoom_position: Option<ControlPosition>,
+	#[cfg_attr(feature = "serde", serde(rename = "map_type_control"))]
+	map_type: Option<bool>,
+	#[cfg_attr(feature = "serde", serde(rename = "map_type_control_position"))]
+	map_type_position: Option<ControlPosition>,
+	#[cfg_attr(feature = "serde", serde(rename = "scale_control"))]
+	scale: Option<bool>,
+	#[cfg_attr(feature = "serde", serde(rename = "street_view_control"))]
+	street_view: Option<bool>,
+	#[cfg_attr(feature = "serde", serde(rename = "street_view_control_position"))]
+	street_view_position: Option<ControlPosition>,
+	#[cfg_attr(feature = "serde", serde(rename = "fullscreen_control"))]
+	fullscreen: Option<bool>,
+	#[cfg_attr(feature = "serde", serde(rename = "fullscreen_control_position"))]
+	fullscreen_position: Option<ControlPosition>,
+}
+
+/// `google.maps.*ControlOptions`
+#[derive(Debug, Copy, Clone)]
+struct ControlOptions(ControlPosition);
+
+impl JavaScript for ControlOptions {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_object().entry("position", &self.0).finish()
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Restriction {
+	bounds: BoundingBox,
+	strict_bounds: bool,
+}
+
+impl JavaScript for Restriction {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_object()
+			.entry("latLngBounds", &self.bounds)
+			.entry("strictBounds", &self.strict_bounds)
+			.finish()
+	}
+}
+
 ////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
 
 pub trait Shape: Debug {
 	#[allow(clippy::missing_errors_doc)]
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result;
+
+	/// The serializable representation of the shape, `None` if the shape can't be serialized.
+	#[cfg(feature = "serde")]
+	#[doc(hidden)]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		None
+	}
+
+	/// The start and end of the time during which the shape is shown by the [`TimeSlider`], `None` if the shape is
+	/// always shown.
+	#[doc(hidden)]
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		None
+	}
+
+	/// The smallest box containing the shape, used by [`GoogleMap::fit_shapes`].
+	#[doc(hidden)]
+	fn bounds(&self) -> Option<BoundingBox> {
+		None
+	}
+
+	/// The event listeners of the shape.
+	#[doc(hidden)]
+	fn events(&self) -> &[(Event, Action)] {
+		&[]
+	}
+
+	/// The ID of the shape, if set.
+	#[doc(hidden)]
+	fn shape_id(&self) -> Option<&str> {
+		None
+	}
+
+	/// Whether the user can change the shape on the map, i.e. it's editable or draggable.
+	#[doc(hidden)]
+	fn is_editable(&self) -> bool {
+		false
+	}
+
+	/// The shapes drawn as part of the shape with their own event listeners and IDs, e.g. the regions of a
+	/// [`Choropleth`](choropleth::Choropleth).
+	#[doc(hidden)]
+	fn parts(&self) -> Vec<&dyn Shape> {
+		Vec::new()
+	}
 }
 
-#[derive(Default, Debug, Copy, Clone)]
-struct CommonOptions {
+impl<S: Shape + ?Sized> Shape for Box<S> {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		(**self).fmt_js(f)
+	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		(**self).serde_repr()
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		(**self).time_range()
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		(**self).bounds()
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		(**self).events()
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		(**self).shape_id()
+	}
+
+	fn is_editable(&self) -> bool {
+		(**self).is_editable()
+	}
+
+	fn parts(&self) -> Vec<&dyn Shape> {
+		(**self).parts()
+	}
+}
+
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Default, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct CommonOptions {
+	id: Option<String>,
 	// // TODO: this would have no effect
 	// clickable: Option<bool>,
 	draggable: Option<bool>,
 	// TODO: Marker has everything but this
 	editable: Option<bool>,
-	visible: Option<bool>,
+	pub(crate) visible: Option<bool>,
 	z_index: Option<isize>,
+	time: Option<TimeRange>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	events: Vec<(Event, Action)>,
 }
 
 /// Marker.
@@ -198,13 +1146,23 @@ struct CommonOptions {
 ///
 /// std::fs::write("map.html", html).unwrap();
 /// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Marker {
-	position: Location,
-	label: Option<String>,
-	title: Option<String>,
-	opacity: Option<f64>,
+	id: Option<String>,
+	pub(crate) position: Location,
+	pub(crate) label: Option<String>,
+	pub(crate) title: Option<String>,
+	pub(crate) opacity: Option<f64>,
+	draggable: Option<bool>,
 	z_index: Option<isize>,
+	time: Option<TimeRange>,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	events: Vec<(Event, Action)>,
 }
 
 impl Marker {
@@ -212,11 +1170,15 @@ impl Marker {
 	#[must_use]
 	pub fn new(pos: impl Into<Location>) -> Self {
 		Marker {
+			id: None,
 			position: pos.into(),
 			label: None,
 			title: None,
 			opacity: None,
+			draggable: None,
 			z_index: None,
+			time: None,
+			events: Vec::new(),
 		}
 	}
 
@@ -233,20 +1195,123 @@ impl Marker {
 		self.title = Some(value.as_ref().to_string());
 		self
 	}
+
+	/// An ID identifying this marker, e.g. in the GeoJSON downloaded from the page if it's [draggable](Marker::draggable),
+	/// see [`edits`].
+	///
+	/// Within the page, shapes with an ID are registered in the `__shapes` object (also `window.__shapes`) and assigned
+	/// to a variable named `$` followed by the ID, where characters other than ASCII letters, digits, `_` and `$` are
+	/// replaced by `_`. Scripts of [event listeners](Marker::on) can refer to them that way. If several shapes share an
+	/// ID or a variable name, only the first one is registered under it.
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::google::{Action, Event, GoogleMap, Marker};
+	///
+	/// let html = GoogleMap::new((51.5, -0.5), 8, "<your-apikey-here>")
+	///     .draw(Marker::new((51.507, -0.127)).id("london"))
+	///     .draw(Marker::new((51.752, -1.258)).on(Event::Click, Action::Script("$london.setMap(null);".to_string())))
+	///     .to_string();
+	///
+	/// assert!(html.contains(r#"const $london = __id("london", new google.maps.Marker("#));
+	/// ```
+	#[must_use]
+	pub fn id(mut self, value: impl AsRef<str>) -> Self {
+		self.id = Some(value.as_ref().to_string());
+		self
+	}
+
+	/// If set to `true`, the user can drag this marker over the map. Defaults to `false`.
+	/// Draggable markers with an ID are included in the GeoJSON downloaded from the page, see [`edits`].
+	///
+	/// # Examples
+	/// ```
+	/// use mapplot::google::{GoogleMap, Marker};
+	///
+	/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+	///     .draw(Marker::new((51.507, -0.127)).id("depot").draggable(true))
+	///     .to_string();
+	///
+	/// assert!(html.contains("draggable: true"));
+	/// assert!(html.contains(r#"__edit("depot", new google.maps.Marker("#));
+	/// ```
+	#[must_use]
+	pub fn draggable(mut self, value: bool) -> Self {
+		self.draggable = Some(value);
+		self
+	}
+
+	/// The time at which this marker is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time(mut self, value: SystemTime) -> Self {
+		self.time = Some(TimeRange::new(value, value));
+		self
+	}
+
+	/// The time range during which this marker is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time_range(mut self, start: SystemTime, end: SystemTime) -> Self {
+		self.time = Some(TimeRange::new(start, end));
+		self
+	}
+
+	/// Perform an action when an event occurs on this marker. Can be called multiple times.
+	#[must_use]
+	pub fn on(mut self, event: Event, action: Action) -> Self {
+		self.events.push((event, action));
+		self
+	}
 }
 
-impl Shape for Marker {
-	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
-		f.write_str("new google.maps.Marker(")?;
+impl Marker {
+	/// Write the `google.maps.MarkerOptions` of this marker.
+	pub(crate) fn fmt_options(
+		&self,
+		f: &mut Formatter<'_>,
+		map: Option<RawIdent<'_>>,
+	) -> fmt::Result {
 		f.write_object()
-			.entry("map", &MAP_IDENT)
+			.entry_opt("map", &map)
 			.entry("position", &self.position)
 			.entry_opt("label", &self.label)
 			.entry_opt("title", &self.title)
-			.finish()?;
+			.entry_opt("draggable", &self.draggable)
+			.finish()
+	}
+}
+
+impl Shape for Marker {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("new google.maps.Marker(")?;
+		self.fmt_options(f, Some(MAP_IDENT))?;
 		f.write_str(")")?;
 		Ok(())
 	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		Some(serde_shapes::ShapeRepr::Marker(self))
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		self.time.map(TimeRange::bounds)
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		BoundingBox::around([&self.position])
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		&self.events
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		self.id.as_deref()
+	}
+
+	fn is_editable(&self) -> bool {
+		self.draggable == Some(true)
+	}
 }
 
 impl From<Marker> for Location {
@@ -267,12 +1332,21 @@ impl From<Marker> for Location {
 ///
 /// std::fs::write("map.html", html).unwrap();
 /// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polyline {
-	path: Vec<Location>,
+	pub(crate) path: Vec<Location>,
 	geodesic: Option<bool>,
-	style: PolylineStyle,
-	common: CommonOptions,
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub(crate) style: PolylineStyle,
+	#[cfg_attr(
+		feature = "serde",
+		serde(default, skip_serializing_if = "Vec::is_empty")
+	)]
+	icons: Vec<IconSequence>,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub(crate) common: CommonOptions,
 }
 
 impl Polyline {
@@ -283,6 +1357,7 @@ impl Polyline {
 			path: points.into_iter().map(Into::into).collect(),
 			geodesic: None,
 			style: PolylineStyle::default(),
+			icons: Vec::new(),
 			common: CommonOptions::default(),
 		}
 	}
@@ -301,6 +1376,21 @@ impl Polyline {
 		self
 	}
 
+	/// Draw symbols along the polyline, e.g. arrowheads or dashes. Can be called multiple times.
+	#[must_use]
+	pub fn icon(mut self, value: IconSequence) -> Self {
+		self.icons.push(value);
+		self
+	}
+
+	/// An ID identifying this shape, naming it in the scripts of the page like the [ID of a marker](Marker::id).
+	/// Editable and draggable shapes with an ID are included in the GeoJSON downloaded from the page, see [`edits`].
+	#[must_use]
+	pub fn id(mut self, value: impl AsRef<str>) -> Self {
+		self.common.id = Some(value.as_ref().to_string());
+		self
+	}
+
 	/// If set to `true`, the user can drag this shape over the map. The `geodesic` property defines the mode of dragging. Defaults to `false`.
 	#[must_use]
 	pub fn draggable(mut self, value: bool) -> Self {
@@ -328,18 +1418,41 @@ impl Polyline {
 		self.common.z_index = Some(value);
 		self
 	}
+
+	/// The time at which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time(mut self, value: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(value, value));
+		self
+	}
+
+	/// The time range during which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time_range(mut self, start: SystemTime, end: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(start, end));
+		self
+	}
+
+	/// Perform an action when an event occurs on this shape. Can be called multiple times.
+	#[must_use]
+	pub fn on(mut self, event: Event, action: Action) -> Self {
+		self.common.events.push((event, action));
+		self
+	}
 }
 
 impl Shape for Polyline {
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (stroke_color, stroke_opacity) = self.style.stroke();
 		f.write_str("new google.maps.Polyline(")?;
 		f.write_object()
 			.entry("map", &MAP_IDENT)
 			.entry("path", &self.path)
 			.entry_opt("geodesic", &self.geodesic)
-			.entry_opt("strokeColor", &self.style.stroke_color)
-			.entry_opt("strokeOpacity", &self.style.stroke_opacity)
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
 			.entry_opt("strokeWeight", &self.style.stroke_weight)
+			.entry_opt("icons", &(!self.icons.is_empty()).then_some(&self.icons))
 			.entry_opt("draggable", &self.common.draggable)
 			.entry_opt("editable", &self.common.editable)
 			.entry_opt("visible", &self.common.visible)
@@ -348,6 +1461,31 @@ impl Shape for Polyline {
 		f.write_str(")")?;
 		Ok(())
 	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		Some(serde_shapes::ShapeRepr::Polyline(self))
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		self.common.time.map(TimeRange::bounds)
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		BoundingBox::around(&self.path)
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		&self.common.events
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		self.common.id.as_deref()
+	}
+
+	fn is_editable(&self) -> bool {
+		self.common.editable == Some(true) || self.common.draggable == Some(true)
+	}
 }
 
 /// A geodesic or non-geodesic polygon.
@@ -365,12 +1503,16 @@ impl Shape for Polyline {
 ///
 /// std::fs::write("map.html", html).unwrap();
 /// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Polygon {
-	paths: Vec<Vec<Location>>,
+	pub(crate) paths: Vec<Vec<Location>>,
 	geodesic: Option<bool>,
-	style: PolygonStyle,
-	common: CommonOptions,
+	#[cfg_attr(feature = "serde", serde(default))]
+	pub(crate) style: PolygonStyle,
+	#[cfg_attr(feature = "serde", serde(flatten))]
+	pub(crate) common: CommonOptions,
 }
 
 impl Polygon {
@@ -407,6 +1549,14 @@ impl Polygon {
 		self
 	}
 
+	/// An ID identifying this shape, naming it in the scripts of the page like the [ID of a marker](Marker::id).
+	/// Editable and draggable shapes with an ID are included in the GeoJSON downloaded from the page, see [`edits`].
+	#[must_use]
+	pub fn id(mut self, value: impl AsRef<str>) -> Self {
+		self.common.id = Some(value.as_ref().to_string());
+		self
+	}
+
 	/// If set to `true`, the user can drag this shape over the map. The `geodesic` property defines the mode of dragging. Defaults to `false`.
 	#[must_use]
 	pub fn draggable(mut self, value: bool) -> Self {
@@ -434,20 +1584,43 @@ impl Polygon {
 		self.common.z_index = Some(value);
 		self
 	}
+
+	/// The time at which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time(mut self, value: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(value, value));
+		self
+	}
+
+	/// The time range during which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time_range(mut self, start: SystemTime, end: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(start, end));
+		self
+	}
+
+	/// Perform an action when an event occurs on this shape. Can be called multiple times.
+	#[must_use]
+	pub fn on(mut self, event: Event, action: Action) -> Self {
+		self.common.events.push((event, action));
+		self
+	}
 }
 
 impl Shape for Polygon {
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (fill_color, fill_opacity) = self.style.fill();
+		let (stroke_color, stroke_opacity) = self.style.stroke();
 		f.write_str("new google.maps.Polygon(")?;
 		f.write_object()
 			.entry("map", &MAP_IDENT)
 			.entry("paths", &self.paths)
 			.entry_opt("geodesic", &self.geodesic)
-			.entry_opt("fillColor", &self.style.fill_color)
-			.entry_opt("fillOpacity", &self.style.fill_opacity)
+			.entry_opt("fillColor", &fill_color)
+			.entry_opt("fillOpacity", &fill_opacity)
 			.entry_opt("strokePosition", &self.style.stroke_position)
-			.entry_opt("strokeColor", &self.style.stroke_color)
-			.entry_opt("strokeOpacity", &self.style.stroke_opacity)
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
 			.entry_opt("strokeWeight", &self.style.stroke_weight)
 			.entry_opt("draggable", &self.common.draggable)
 			.entry_opt("editable", &self.common.editable)
@@ -457,6 +1630,31 @@ impl Shape for Polygon {
 		f.write_str(")")?;
 		Ok(())
 	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		Some(serde_shapes::ShapeRepr::Polygon(self))
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		self.common.time.map(TimeRange::bounds)
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		BoundingBox::around(self.paths.iter().flatten())
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		&self.common.events
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		self.common.id.as_deref()
+	}
+
+	fn is_editable(&self) -> bool {
+		self.common.editable == Some(true) || self.common.draggable == Some(true)
+	}
 }
 
 /// A rectangle overlay.
@@ -471,10 +1669,14 @@ impl Shape for Polygon {
 ///
 /// std::fs::write("map.html", html).unwrap();
 /// ```
-#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
-	bounds: BoundingBox,
+	pub(crate) bounds: BoundingBox,
+	#[cfg_attr(feature = "serde", serde(default))]
 	style: PolygonStyle,
+	#[cfg_attr(feature = "serde", serde(flatten))]
 	common: CommonOptions,
 }
 
@@ -496,6 +1698,14 @@ impl Rectangle {
 		self
 	}
 
+	/// An ID identifying this shape, naming it in the scripts of the page like the [ID of a marker](Marker::id).
+	/// Editable and draggable shapes with an ID are included in the GeoJSON downloaded from the page, see [`edits`].
+	#[must_use]
+	pub fn id(mut self, value: impl AsRef<str>) -> Self {
+		self.common.id = Some(value.as_ref().to_string());
+		self
+	}
+
 	/// If set to `true`, the user can drag this rectangle over the map. Defaults to `false`.
 	#[must_use]
 	pub fn draggable(mut self, value: bool) -> Self {
@@ -523,19 +1733,42 @@ impl Rectangle {
 		self.common.z_index = Some(value);
 		self
 	}
+
+	/// The time at which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time(mut self, value: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(value, value));
+		self
+	}
+
+	/// The time range during which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time_range(mut self, start: SystemTime, end: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(start, end));
+		self
+	}
+
+	/// Perform an action when an event occurs on this shape. Can be called multiple times.
+	#[must_use]
+	pub fn on(mut self, event: Event, action: Action) -> Self {
+		self.common.events.push((event, action));
+		self
+	}
 }
 
 impl Shape for Rectangle {
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (fill_color, fill_opacity) = self.style.fill();
+		let (stroke_color, stroke_opacity) = self.style.stroke();
 		f.write_str("new google.maps.Rectangle(")?;
 		f.write_object()
 			.entry("map", &MAP_IDENT)
 			.entry("bounds", &self.bounds)
-			.entry_opt("fillColor", &self.style.fill_color)
-			.entry_opt("fillOpacity", &self.style.fill_opacity)
+			.entry_opt("fillColor", &fill_color)
+			.entry_opt("fillOpacity", &fill_opacity)
 			.entry_opt("strokePosition", &self.style.stroke_position)
-			.entry_opt("strokeColor", &self.style.stroke_color)
-			.entry_opt("strokeOpacity", &self.style.stroke_opacity)
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
 			.entry_opt("strokeWeight", &self.style.stroke_weight)
 			.entry_opt("draggable", &self.common.draggable)
 			.entry_opt("editable", &self.common.editable)
@@ -545,6 +1778,48 @@ impl Shape for Rectangle {
 		f.write_str(")")?;
 		Ok(())
 	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		Some(serde_shapes::ShapeRepr::Rectangle(self))
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		self.common.time.map(TimeRange::bounds)
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		BoundingBox::around([&self.bounds.p1, &self.bounds.p2])
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		&self.common.events
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		self.common.id.as_deref()
+	}
+
+	fn is_editable(&self) -> bool {
+		self.common.editable == Some(true) || self.common.draggable == Some(true)
+	}
+}
+
+impl From<Rectangle> for Polygon {
+	fn from(r: Rectangle) -> Self {
+		let BoundingBox { p1, p2 } = r.bounds;
+		Polygon {
+			paths: vec![vec![
+				p1,
+				Location::new(p1.lat, p2.lon),
+				p2,
+				Location::new(p2.lat, p1.lon),
+			]],
+			geodesic: None,
+			style: r.style,
+			common: r.common,
+		}
+	}
 }
 
 /// A circle on the Earth's surface; also known as a "spherical cap".
@@ -559,11 +1834,15 @@ impl Shape for Rectangle {
 ///
 /// std::fs::write("map.html", html).unwrap();
 /// ```
-#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Circle {
 	center: Location,
 	radius: f64,
+	#[cfg_attr(feature = "serde", serde(default))]
 	style: PolygonStyle,
+	#[cfg_attr(feature = "serde", serde(flatten))]
 	common: CommonOptions,
 }
 
@@ -588,6 +1867,14 @@ impl Circle {
 		self
 	}
 
+	/// An ID identifying this shape, naming it in the scripts of the page like the [ID of a marker](Marker::id).
+	/// Editable and draggable shapes with an ID are included in the GeoJSON downloaded from the page, see [`edits`].
+	#[must_use]
+	pub fn id(mut self, value: impl AsRef<str>) -> Self {
+		self.common.id = Some(value.as_ref().to_string());
+		self
+	}
+
 	/// If set to `true`, the user can drag this circle over the map. Defaults to `false`.
 	#[must_use]
 	pub fn draggable(mut self, value: bool) -> Self {
@@ -615,20 +1902,43 @@ impl Circle {
 		self.common.z_index = Some(value);
 		self
 	}
+
+	/// The time at which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time(mut self, value: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(value, value));
+		self
+	}
+
+	/// The time range during which this shape is shown by the [`TimeSlider`].
+	#[must_use]
+	pub fn time_range(mut self, start: SystemTime, end: SystemTime) -> Self {
+		self.common.time = Some(TimeRange::new(start, end));
+		self
+	}
+
+	/// Perform an action when an event occurs on this shape. Can be called multiple times.
+	#[must_use]
+	pub fn on(mut self, event: Event, action: Action) -> Self {
+		self.common.events.push((event, action));
+		self
+	}
 }
 
 impl Shape for Circle {
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (fill_color, fill_opacity) = self.style.fill();
+		let (stroke_color, stroke_opacity) = self.style.stroke();
 		f.write_str("new google.maps.Circle(")?;
 		f.write_object()
 			.entry("map", &MAP_IDENT)
 			.entry("center", &self.center)
 			.entry("radius", &self.radius)
-			.entry_opt("fillColor", &self.style.fill_color)
-			.entry_opt("fillOpacity", &self.style.fill_opacity)
+			.entry_opt("fillColor", &fill_color)
+			.entry_opt("fillOpacity", &fill_opacity)
 			.entry_opt("strokePosition", &self.style.stroke_position)
-			.entry_opt("strokeColor", &self.style.stroke_color)
-			.entry_opt("strokeOpacity", &self.style.stroke_opacity)
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
 			.entry_opt("strokeWeight", &self.style.stroke_weight)
 			.entry_opt("draggable", &self.common.draggable)
 			.entry_opt("editable", &self.common.editable)
@@ -638,4 +1948,35 @@ impl Shape for Circle {
 		f.write_str(")")?;
 		Ok(())
 	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<serde_shapes::ShapeRepr<'_>> {
+		Some(serde_shapes::ShapeRepr::Circle(self))
+	}
+
+	fn time_range(&self) -> Option<(SystemTime, SystemTime)> {
+		self.common.time.map(TimeRange::bounds)
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		// meters per degree of latitude, on a sphere with the mean radius of the earth
+		let lat = self.radius / 111_195.0;
+		let lon = lat / self.center.lat.to_radians().cos().max(1e-6);
+		BoundingBox::around([
+			&Location::new(self.center.lat - lat, self.center.lon - lon),
+			&Location::new(self.center.lat + lat, self.center.lon + lon),
+		])
+	}
+
+	fn events(&self) -> &[(Event, Action)] {
+		&self.common.events
+	}
+
+	fn shape_id(&self) -> Option<&str> {
+		self.common.id.as_deref()
+	}
+
+	fn is_editable(&self) -> bool {
+		self.common.editable == Some(true) || self.common.draggable == Some(true)
+	}
 }
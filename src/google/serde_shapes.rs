@@ -0,0 +1,78 @@
+//! (De)serialization of the trait objects in [`GoogleMap::shapes`](super::GoogleMap).
+//!
+//! Every shape is serialized as an object with a `type` field naming the shape, followed by the fields of the shape.
+
+use serde::ser::{Error as _, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::geometry::Geometry;
+use crate::google::choropleth::Choropleth;
+use crate::google::{
+	AnimatedTrack, Circle, Marker, MarkerCluster, Polygon, Polyline, Rectangle, Shape,
+};
+
+#[doc(hidden)]
+#[derive(Debug, Serialize)]
+#[serde(tag = "type")]
+pub enum ShapeRepr<'a> {
+	Marker(&'a Marker),
+	Polyline(&'a Polyline),
+	Polygon(&'a Polygon),
+	Rectangle(&'a Rectangle),
+	Circle(&'a Circle),
+	MarkerCluster(&'a MarkerCluster),
+	AnimatedTrack(&'a AnimatedTrack),
+	Choropleth(&'a Choropleth),
+	Geometry { geometry: &'a Geometry },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum OwnedShape {
+	Marker(Marker),
+	Polyline(Polyline),
+	Polygon(Polygon),
+	Rectangle(Rectangle),
+	Circle(Circle),
+	MarkerCluster(MarkerCluster),
+	AnimatedTrack(AnimatedTrack),
+	Choropleth(Choropleth),
+	Geometry { geometry: Geometry },
+}
+
+impl From<OwnedShape> for Box<dyn Shape> {
+	fn from(shape: OwnedShape) -> Self {
+		match shape {
+			OwnedShape::Marker(s) => Box::new(s),
+			OwnedShape::Polyline(s) => Box::new(s),
+			OwnedShape::Polygon(s) => Box::new(s),
+			OwnedShape::Rectangle(s) => Box::new(s),
+			OwnedShape::Circle(s) => Box::new(s),
+			OwnedShape::MarkerCluster(s) => Box::new(s),
+			OwnedShape::AnimatedTrack(s) => Box::new(s),
+			OwnedShape::Choropleth(s) => Box::new(s),
+			OwnedShape::Geometry { geometry } => Box::new(geometry),
+		}
+	}
+}
+
+pub(crate) fn serialize<S: Serializer>(
+	shapes: &[Box<dyn Shape>],
+	serializer: S,
+) -> Result<S::Ok, S::Error> {
+	let mut seq = serializer.serialize_seq(Some(shapes.len()))?;
+	for shape in shapes {
+		let repr = shape
+			.serde_repr()
+			.ok_or_else(|| S::Error::custom(format!("shape can't be serialized: {shape:?}")))?;
+		seq.serialize_element(&repr)?;
+	}
+	seq.end()
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+	deserializer: D,
+) -> Result<Vec<Box<dyn Shape>>, D::Error> {
+	let shapes = Vec::<OwnedShape>::deserialize(deserializer)?;
+	Ok(shapes.into_iter().map(Into::into).collect())
+}
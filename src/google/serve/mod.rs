@@ -0,0 +1,620 @@
+//! A local preview server with live reload and streamed shapes, enabled by the `serve` feature.
+//!
+//! The [`Server`] hosts a [`GoogleMap`] on a local address. Open pages are connected to the server with a WebSocket
+//! and reload whenever the program pushes an updated map with [`Server::update`], or when the server is restarted.
+//! Everything runs in-process, without any external services.
+//!
+//! For maps changing frequently, e.g. monitoring dashboards, shapes can also be streamed into the open pages without
+//! reloading them: a [`Message`] adds, updates or removes a shape identified by an ID. Messages are sent by the
+//! program with [`Server::send`], or by a [`Client`] connected to the server, e.g. from another process.
+//!
+//! # Examples
+//! ```no_run
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! use mapplot::google::serve::{Message, Server};
+//! use mapplot::google::{GoogleMap, Marker};
+//!
+//! let server = Server::bind("127.0.0.1:8080").unwrap();
+//! println!("open http://{}", server.addr());
+//! server.update(&GoogleMap::new((51.5, -0.1), 12, "<your-apikey-here>"));
+//!
+//! for i in 0..60 {
+//!     let position = (51.5 + f64::from(i) * 0.001, -0.1);
+//!     server.send(Message::add("bus-1", &Marker::new(position).title("Bus 1")));
+//!     thread::sleep(Duration::from_secs(1));
+//! }
+//! server.send(Message::remove("bus-1"));
+//! ```
+
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::google::{fmt_listened_shape, GoogleMap, Shape};
+use crate::{hijack_formatter, json};
+
+mod websocket;
+
+/// The path of the WebSocket connecting the pages to the server.
+const LIVE_PATH: &str = "/__live";
+/// How many frames may wait for a connection, on top of the streamed shapes sent to it initially, before the
+/// connection is dropped.
+const QUEUE_SIZE: usize = 1024;
+/// How long a write may block before the connection is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The opcode and the payload of a frame waiting to be written.
+type Frame = (u8, Vec<u8>);
+
+/// A local HTTP server showing a map, see the [module documentation](self).
+///
+/// Until the first [`update`](Server::update), the page shows a placeholder. The server runs in background threads
+/// until the program exits.
+///
+/// Messages received from [`Client`]s are applied like the ones sent with [`send`](Server::send), and the shapes
+/// they contain run as scripts in the open pages. WebSocket connections from pages of other origins are rejected,
+/// but any program able to reach the address can connect, so bind to a loopback address like `127.0.0.1` unless the
+/// network is trusted.
+///
+/// Each connection is written by its own thread, so a page or client which stops reading, e.g. a frozen tab, doesn't
+/// hold up the others: it is disconnected once its messages pile up or a write blocks for more than 10 seconds.
+#[derive(Debug)]
+pub struct Server {
+	addr: SocketAddr,
+	state: Arc<Mutex<State>>,
+	accept: JoinHandle<()>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+	page: Option<String>,
+	/// The [`Message::Add`]s of the shapes currently streamed into the map, sent to every new connection.
+	shapes: Vec<Message>,
+	clients: Vec<Connection>,
+	next_client: usize,
+}
+
+/// A WebSocket connection registered with the [`State`], written by a thread of its own, see [`write_queued`].
+#[derive(Debug)]
+struct Connection {
+	id: usize,
+	queue: SyncSender<Frame>,
+	stream: TcpStream,
+}
+
+impl Connection {
+	/// Queue a frame, shutting the connection down if it cannot keep up. Returns whether the frame was queued.
+	fn send(&self, opcode: u8, payload: &[u8]) -> bool {
+		let queued = self.queue.try_send((opcode, payload.to_vec())).is_ok();
+		if !queued {
+			let _ = self.stream.shutdown(Shutdown::Both);
+		}
+		queued
+	}
+}
+
+impl State {
+	/// Apply a message to the streamed shapes and send it to all connections except the `sender`.
+	fn apply(&mut self, message: Message, sender: Option<usize>) {
+		let text = message.to_string();
+		match &message {
+			Message::Add { id, .. } => {
+				let id = id.clone();
+				self.shapes.retain(|m| m.id() != Some(&id));
+				self.shapes.push(message);
+			}
+			Message::Update { id, shape } => {
+				if let Some(Message::Add { shape: current, .. }) =
+					self.shapes.iter_mut().find(|m| m.id() == Some(id))
+				{
+					current.clone_from(shape);
+				}
+			}
+			Message::Remove { id } => {
+				let id = id.clone();
+				self.shapes.retain(|m| m.id() != Some(&id));
+			}
+			Message::Reload => {}
+		}
+		self.broadcast(&text, sender);
+	}
+
+	/// Send a text message to all connections except the `sender`, dropping the disconnected and stalled ones.
+	fn broadcast(&mut self, message: &str, sender: Option<usize>) {
+		self.clients.retain(|client| {
+			Some(client.id) == sender || client.send(websocket::TEXT, message.as_bytes())
+		});
+	}
+}
+
+impl Server {
+	/// Start a server listening on `addr`, e.g. `"127.0.0.1:8080"`. Use port 0 to pick any free port, see
+	/// [`addr`](Server::addr).
+	///
+	/// # Errors
+	/// Fails if the address cannot be bound.
+	pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+		let listener = TcpListener::bind(addr)?;
+		let addr = listener.local_addr()?;
+		let state = Arc::new(Mutex::new(State::default()));
+
+		let accept = {
+			let state = Arc::clone(&state);
+			thread::spawn(move || {
+				for stream in listener.incoming().flatten() {
+					let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+					let state = Arc::clone(&state);
+					thread::spawn(move || handle(stream, &state));
+				}
+			})
+		};
+
+		Ok(Server {
+			addr,
+			state,
+			accept,
+		})
+	}
+
+	/// The address the server is listening on.
+	///
+	/// # Examples
+	/// ```
+	/// use std::io::{Read, Write};
+	/// use std::net::TcpStream;
+	///
+	/// use mapplot::google::serve::Server;
+	/// use mapplot::google::GoogleMap;
+	///
+	/// let server = Server::bind("127.0.0.1:0").unwrap();
+	/// server.update(GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>").page_title("Preview"));
+	///
+	/// let mut stream = TcpStream::connect(server.addr()).unwrap();
+	/// stream.write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+	/// let mut response = String::new();
+	/// stream.read_to_string(&mut response).unwrap();
+	///
+	/// assert!(response.starts_with("HTTP/1.1 200 OK"));
+	/// assert!(response.contains("<title>Preview</title>"));
+	/// ```
+	#[must_use]
+	pub fn addr(&self) -> SocketAddr {
+		self.addr
+	}
+
+	/// Show `map` on the page, reloading all open pages. The streamed shapes are kept.
+	pub fn update(&self, map: &GoogleMap) {
+		let page = hijack_formatter(|f| map.fmt_page(f, Some(&live_script())));
+		let mut state = lock(&self.state);
+		state.page = Some(page);
+		state.apply(Message::Reload, None);
+	}
+
+	/// Apply a message to the map in all open pages, without reloading them.
+	pub fn send(&self, message: Message) {
+		lock(&self.state).apply(message, None);
+	}
+
+	/// Block the current thread while the server is running, i.e. until the program is terminated.
+	pub fn wait(self) {
+		let _ = self.accept.join();
+	}
+}
+
+impl GoogleMap {
+	/// Serve the map on a local address, e.g. `"127.0.0.1:8080"`, until the program is terminated. Open pages reload
+	/// when the program is restarted; use a [`Server`] to update the map without restarting.
+	///
+	/// # Errors
+	/// Fails if the address cannot be bound.
+	///
+	/// # Examples
+	/// ```no_run
+	/// use mapplot::google::{GoogleMap, Marker};
+	///
+	/// GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+	///     .draw(Marker::new((51.507, -0.127)))
+	///     .serve("127.0.0.1:8080")
+	///     .unwrap();
+	/// ```
+	pub fn serve(&self, addr: impl ToSocketAddrs) -> io::Result<()> {
+		let server = Server::bind(addr)?;
+		server.update(self);
+		server.wait();
+		Ok(())
+	}
+}
+
+/// An incremental change of the map shown by a [`Server`].
+///
+/// Shapes are identified by IDs chosen by the sender, unrelated to the IDs of the shapes themselves. Within the page, the
+/// streamed shapes are registered in `__shapes` under these IDs, see [`Marker::id`](crate::google::Marker::id).
+/// Markers, polylines, polygons, rectangles, circles and geometries can be streamed; the shapes with their own
+/// controls, e.g. an [`AnimatedTrack`](crate::google::AnimatedTrack), cannot be updated or removed.
+///
+/// On the wire, messages are JSON objects like `{"type": "add", "id": "bus-1", "shape": "new google.maps.Marker(...)"}`,
+/// `{"type": "remove", "id": "bus-1"}` or `{"type": "reload"}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+	/// Draw a shape, replacing the shape with the same ID. The shape is a JavaScript expression, see [`Message::add`].
+	Add { id: String, shape: String },
+	/// Replace the shape with the given ID. Ignored if there is no such shape, e.g. because it was removed.
+	Update { id: String, shape: String },
+	/// Remove the shape with the given ID.
+	Remove { id: String },
+	/// Reload the page, e.g. after the map was replaced with [`Server::update`].
+	Reload,
+}
+
+impl Message {
+	/// Draw `shape` with the given ID, replacing the shape with the same ID.
+	#[must_use]
+	pub fn add(id: impl AsRef<str>, shape: &impl Shape) -> Self {
+		Message::Add {
+			id: id.as_ref().to_string(),
+			shape: hijack_formatter(|f| fmt_listened_shape(f, shape)),
+		}
+	}
+
+	/// Replace the shape with the given ID by `shape`, unless the shape was removed or never added.
+	#[must_use]
+	pub fn update(id: impl AsRef<str>, shape: &impl Shape) -> Self {
+		Message::Update {
+			id: id.as_ref().to_string(),
+			shape: hijack_formatter(|f| fmt_listened_shape(f, shape)),
+		}
+	}
+
+	/// Remove the shape with the given ID.
+	#[must_use]
+	pub fn remove(id: impl AsRef<str>) -> Self {
+		Message::Remove {
+			id: id.as_ref().to_string(),
+		}
+	}
+
+	/// The ID of the shape changed by this message.
+	#[must_use]
+	pub fn id(&self) -> Option<&str> {
+		match self {
+			Message::Add { id, .. } | Message::Update { id, .. } | Message::Remove { id } => {
+				Some(id)
+			}
+			Message::Reload => None,
+		}
+	}
+
+	fn parse(s: &str) -> Option<Self> {
+		let value = json::parse(s).ok()?;
+		let id = || value.get("id")?.as_str().map(str::to_string);
+		let shape = || value.get("shape")?.as_str().map(str::to_string);
+		match value.get("type")?.as_str()? {
+			"add" => Some(Message::Add {
+				id: id()?,
+				shape: shape()?,
+			}),
+			"update" => Some(Message::Update {
+				id: id()?,
+				shape: shape()?,
+			}),
+			"remove" => Some(Message::Remove { id: id()? }),
+			"reload" => Some(Message::Reload),
+			_ => None,
+		}
+	}
+}
+
+impl Display for Message {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Message::Add { id, shape } | Message::Update { id, shape } => {
+				let kind = if matches!(self, Message::Add { .. }) {
+					"add"
+				} else {
+					"update"
+				};
+				write!(
+					f,
+					r#"{{"type": "{kind}", "id": {}, "shape": {}}}"#,
+					json::quote(id),
+					json::quote(shape)
+				)
+			}
+			Message::Remove { id } => {
+				write!(f, r#"{{"type": "remove", "id": {}}}"#, json::quote(id))
+			}
+			Message::Reload => f.write_str(r#"{"type": "reload"}"#),
+		}
+	}
+}
+
+/// A WebSocket connection to a [`Server`], e.g. for streaming shapes from another process, or for testing.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::serve::{Client, Message, Server};
+/// use mapplot::google::{GoogleMap, Marker};
+///
+/// let server = Server::bind("127.0.0.1:0").unwrap();
+/// server.update(&GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>"));
+/// server.send(Message::add("bus-1", &Marker::new((51.5, -0.1))));
+///
+/// // a new connection receives the shapes streamed so far
+/// let mut dashboard = Client::connect(server.addr()).unwrap();
+/// let message = dashboard.recv().unwrap();
+/// assert!(matches!(message, Message::Add { ref id, .. } if id == "bus-1"));
+///
+/// // messages of other clients are forwarded
+/// let mut feed = Client::connect(server.addr()).unwrap();
+/// assert_eq!(feed.recv().unwrap(), message);
+/// feed.send(&Message::update("bus-1", &Marker::new((51.6, -0.1)))).unwrap();
+/// assert!(matches!(dashboard.recv().unwrap(), Message::Update { ref id, .. } if id == "bus-1"));
+///
+/// server.send(Message::remove("bus-1"));
+/// assert_eq!(dashboard.recv().unwrap(), Message::remove("bus-1"));
+/// ```
+#[derive(Debug)]
+pub struct Client {
+	stream: TcpStream,
+	reader: BufReader<TcpStream>,
+}
+
+impl Client {
+	/// Connect to the server at `addr`. The server first sends a [`Message::Add`] for each shape streamed so far.
+	///
+	/// # Errors
+	/// Fails if the server cannot be reached or doesn't accept the WebSocket connection.
+	pub fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+		let mut stream = TcpStream::connect(addr)?;
+		let key = websocket::client_key();
+		write!(
+			stream,
+			"GET {LIVE_PATH} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {key}\r\nSec-WebSocket-Version: 13\r\n\r\n",
+			stream.peer_addr()?,
+		)?;
+
+		let mut reader = BufReader::new(stream.try_clone()?);
+		let (status, headers) = read_head(&mut reader)?;
+		let accept = header(&headers, "sec-websocket-accept");
+		if status.split_whitespace().nth(1) != Some("101")
+			|| accept != Some(&websocket::accept_key(&key))
+		{
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("the server refused the connection: {}", status.trim_end()),
+			));
+		}
+
+		Ok(Client { stream, reader })
+	}
+
+	/// Send a message to the server, which applies it and forwards it to all other connections.
+	///
+	/// # Errors
+	/// Fails if the connection is lost.
+	pub fn send(&mut self, message: &Message) -> io::Result<()> {
+		websocket::write_frame(
+			&mut self.stream,
+			websocket::TEXT,
+			message.to_string().as_bytes(),
+			true,
+		)
+	}
+
+	/// Wait for the next message from the server.
+	///
+	/// # Errors
+	/// Fails if the connection is lost or closed, or if the server sends an invalid message.
+	pub fn recv(&mut self) -> io::Result<Message> {
+		let stream = &mut self.stream;
+		let message = websocket::read_message(&mut self.reader, |opcode, payload| {
+			websocket::write_frame(stream, opcode, payload, true)
+		})?
+		.ok_or_else(|| {
+			io::Error::new(
+				io::ErrorKind::ConnectionAborted,
+				"the server closed the connection",
+			)
+		})?;
+		String::from_utf8(message)
+			.ok()
+			.and_then(|s| Message::parse(&s))
+			.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "invalid message"))
+	}
+}
+
+fn lock(state: &Mutex<State>) -> MutexGuard<'_, State> {
+	state.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The script connecting a map to the server, applying the received messages. It runs within the initialization of
+/// the map, which makes the map and the helpers available to the streamed shapes.
+fn live_script() -> String {
+	format!(
+		r#"		(function () {{
+			const shapes = new Map();
+			const hide = shape => Array.isArray(shape) ? shape.forEach(hide) : shape && shape.setMap && shape.setMap(null);
+			function apply(message) {{
+				switch (message.type) {{
+					case "reload":
+						return location.reload();
+					case "update":
+						if (!shapes.has(message.id)) return;
+					// falls through
+					case "add":
+						hide(shapes.get(message.id));
+						shapes.set(message.id, eval(message.shape));
+						return __shapes[message.id] = shapes.get(message.id);
+					case "remove":
+						hide(shapes.get(message.id));
+						shapes.delete(message.id);
+						return delete __shapes[message.id];
+				}}
+			}}
+			(function connect(reconnected) {{
+				const socket = new WebSocket((location.protocol === "https:" ? "wss://" : "ws://") + location.host + "{LIVE_PATH}");
+				socket.onopen = () => reconnected && location.reload();
+				socket.onmessage = event => apply(JSON.parse(event.data));
+				socket.onclose = () => setTimeout(() => connect(true), 1000);
+			}})(false);
+		}})();
+"#
+	)
+}
+
+/// The page shown until the first map, reloading once the map is available.
+fn placeholder_page() -> String {
+	format!(
+		r#"<html>
+<body>
+Waiting for the map...
+<script type="text/javascript">
+	(function connect() {{
+		const socket = new WebSocket((location.protocol === "https:" ? "wss://" : "ws://") + location.host + "{LIVE_PATH}");
+		socket.onmessage = event => JSON.parse(event.data).type === "reload" && location.reload();
+		socket.onclose = () => setTimeout(connect, 1000);
+	}})();
+</script>
+</body>
+</html>
+"#
+	)
+}
+
+/// Read the first line and the headers of an HTTP request or response.
+fn read_head(reader: &mut impl BufRead) -> io::Result<(String, Vec<(String, String)>)> {
+	let mut first_line = String::new();
+	reader.read_line(&mut first_line)?;
+
+	let mut headers = Vec::new();
+	loop {
+		let mut line = String::new();
+		if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+			break;
+		}
+		if let Some((name, value)) = line.split_once(':') {
+			headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+		}
+	}
+	Ok((first_line, headers))
+}
+
+/// The value of a header, by its lowercase name.
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a String> {
+	headers.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+}
+
+/// Answer an HTTP request, keeping WebSocket connections open until they are closed.
+fn handle(stream: TcpStream, state: &Mutex<State>) -> io::Result<()> {
+	let mut reader = BufReader::new(stream.try_clone()?);
+	let (request_line, headers) = read_head(&mut reader)?;
+
+	let path = request_line
+		.split_whitespace()
+		.nth(1)
+		.and_then(|target| target.split('?').next())
+		.unwrap_or_default();
+	match (path, header(&headers, "sec-websocket-key")) {
+		(LIVE_PATH, Some(key)) => {
+			// browsers connect from any page, only accept the pages of this server
+			let origin = header(&headers, "origin");
+			let host = header(&headers, "host").map_or("", String::as_str);
+			if origin.is_some_and(|o| {
+				o.strip_prefix("http://")
+					.or_else(|| o.strip_prefix("https://"))
+					!= Some(host)
+			}) {
+				return respond(stream, "403 Forbidden", "Forbidden");
+			}
+			connect(stream, reader, key, state)
+		}
+		("/" | "/index.html", _) => {
+			let page = lock(state).page.clone().unwrap_or_else(placeholder_page);
+			respond(stream, "200 OK", &page)
+		}
+		_ => respond(stream, "404 Not Found", "Not Found"),
+	}
+}
+
+fn respond(mut stream: TcpStream, status: &str, body: &str) -> io::Result<()> {
+	write!(
+		stream,
+		"HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+		body.len(),
+	)?;
+	stream.flush()
+}
+
+/// Complete the WebSocket handshake and register the connection, applying its messages until it disconnects.
+fn connect(
+	mut stream: TcpStream,
+	mut reader: BufReader<TcpStream>,
+	key: &str,
+	state: &Mutex<State>,
+) -> io::Result<()> {
+	write!(
+		stream,
+		"HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+		websocket::accept_key(key),
+	)?;
+
+	let (id, queue, frames) = {
+		let mut state = lock(state);
+		let (queue, frames) = mpsc::sync_channel(state.shapes.len() + QUEUE_SIZE);
+		for message in &state.shapes {
+			// cannot fail, the queue has room for all of them
+			let _ = queue.try_send((websocket::TEXT, message.to_string().into_bytes()));
+		}
+		let id = state.next_client;
+		state.next_client += 1;
+		state.clients.push(Connection {
+			id,
+			queue: queue.clone(),
+			stream: stream.try_clone()?,
+		});
+		(id, queue, frames)
+	};
+	thread::spawn(move || write_queued(stream, &frames));
+
+	let result = loop {
+		// replies are queued like the broadcasts, so that they cannot interleave
+		let message = websocket::read_message(&mut reader, |opcode, payload| {
+			queue
+				.try_send((opcode, payload.to_vec()))
+				.map_err(|_| io::Error::new(io::ErrorKind::WouldBlock, "websocket queue full"))
+		});
+		match message {
+			Ok(Some(message)) => {
+				if let Some(message) = String::from_utf8(message)
+					.ok()
+					.and_then(|s| Message::parse(&s))
+				{
+					lock(state).apply(message, Some(id));
+				}
+			}
+			Ok(None) => break Ok(()),
+			Err(e) => break Err(e),
+		}
+	};
+
+	// the writer finishes the queued frames, e.g. the reply to a close, once both senders are dropped
+	lock(state).clients.retain(|client| client.id != id);
+	result
+}
+
+/// Write the queued frames of a connection until it is unregistered, shutting it down if a write fails or times out,
+/// which also ends the reading in [`connect`].
+fn write_queued(mut stream: TcpStream, frames: &Receiver<Frame>) {
+	for (opcode, payload) in frames {
+		if websocket::write_frame(&mut stream, opcode, &payload, false).is_err() {
+			let _ = stream.shutdown(Shutdown::Both);
+			return;
+		}
+	}
+}
@@ -0,0 +1,226 @@
+//! The parts of the WebSocket protocol (RFC 6455) needed by the server and the client.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Read, Write};
+
+pub(crate) const TEXT: u8 = 0x1;
+pub(crate) const CLOSE: u8 = 0x8;
+pub(crate) const PING: u8 = 0x9;
+pub(crate) const PONG: u8 = 0xa;
+
+/// The largest accepted payload of a frame.
+const MAX_PAYLOAD: u64 = 16 << 20;
+
+#[derive(Debug)]
+pub(crate) struct Frame {
+	pub(crate) fin: bool,
+	pub(crate) opcode: u8,
+	pub(crate) payload: Vec<u8>,
+}
+
+/// The `Sec-WebSocket-Accept` header value answering a `Sec-WebSocket-Key`.
+pub(crate) fn accept_key(key: &str) -> String {
+	base64(&sha1(
+		format!("{key}258EAFA5-E914-47DA-95CA-C5AB0DC85B11").as_bytes(),
+	))
+}
+
+/// A random `Sec-WebSocket-Key` for a client handshake.
+pub(crate) fn client_key() -> String {
+	let mut key = [0; 16];
+	key[..8].copy_from_slice(&random_bytes());
+	key[8..].copy_from_slice(&random_bytes());
+	base64(&key)
+}
+
+/// Write a frame. Servers send unmasked frames, clients must mask them.
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn write_frame(
+	w: &mut impl Write,
+	opcode: u8,
+	payload: &[u8],
+	masked: bool,
+) -> io::Result<()> {
+	let mask_bit = if masked { 0x80 } else { 0 };
+	let mut frame = vec![0x80 | opcode];
+	match payload.len() {
+		len @ 0..=125 => frame.push(mask_bit | len as u8),
+		len @ 126..=0xffff => {
+			frame.push(mask_bit | 0x7e);
+			frame.extend_from_slice(&(len as u16).to_be_bytes());
+		}
+		len => {
+			frame.push(mask_bit | 0x7f);
+			frame.extend_from_slice(&(len as u64).to_be_bytes());
+		}
+	}
+	if masked {
+		let mask = random_bytes();
+		frame.extend_from_slice(&mask[..4]);
+		frame.extend(
+			payload
+				.iter()
+				.enumerate()
+				.map(|(i, byte)| byte ^ mask[i % 4]),
+		);
+	} else {
+		frame.extend_from_slice(payload);
+	}
+	w.write_all(&frame)
+}
+
+/// Read a frame and unmask its payload.
+pub(crate) fn read_frame(r: &mut impl Read) -> io::Result<Frame> {
+	let mut header = [0; 2];
+	r.read_exact(&mut header)?;
+	let len = match header[1] & 0x7f {
+		126 => {
+			let mut len = [0; 2];
+			r.read_exact(&mut len)?;
+			u64::from(u16::from_be_bytes(len))
+		}
+		127 => {
+			let mut len = [0; 8];
+			r.read_exact(&mut len)?;
+			u64::from_be_bytes(len)
+		}
+		len => u64::from(len),
+	};
+	let len = usize::try_from(len)
+		.ok()
+		.filter(|_| len <= MAX_PAYLOAD)
+		.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "websocket frame too large"))?;
+
+	let mut mask = [0; 4];
+	if header[1] & 0x80 != 0 {
+		r.read_exact(&mut mask)?;
+	}
+	let mut payload = vec![0; len];
+	r.read_exact(&mut payload)?;
+	for (i, byte) in payload.iter_mut().enumerate() {
+		*byte ^= mask[i % 4];
+	}
+
+	Ok(Frame {
+		fin: header[0] & 0x80 != 0,
+		opcode: header[0] & 0x0f,
+		payload,
+	})
+}
+
+/// Read the next text or binary message, assembling fragmented ones. Control frames are answered with `reply`.
+/// Returns `None` once the peer closed the connection.
+pub(crate) fn read_message(
+	r: &mut impl Read,
+	mut reply: impl FnMut(u8, &[u8]) -> io::Result<()>,
+) -> io::Result<Option<Vec<u8>>> {
+	let mut message = Vec::new();
+	loop {
+		let frame = read_frame(r)?;
+		match frame.opcode {
+			PING => reply(PONG, &frame.payload)?,
+			PONG => {}
+			CLOSE => {
+				// the peer may already be gone if it initiated the close
+				let _ = reply(CLOSE, &frame.payload);
+				return Ok(None);
+			}
+			_ => {
+				message.extend_from_slice(&frame.payload);
+				if message.len() as u64 > MAX_PAYLOAD {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidData,
+						"websocket message too large",
+					));
+				}
+				if frame.fin {
+					return Ok(Some(message));
+				}
+			}
+		}
+	}
+}
+
+/// Unpredictable bytes for masks and keys, from the randomly seeded hasher of the standard library.
+fn random_bytes() -> [u8; 8] {
+	RandomState::new().build_hasher().finish().to_ne_bytes()
+}
+
+#[allow(clippy::many_single_char_names)]
+fn sha1(data: &[u8]) -> [u8; 20] {
+	let mut h: [u32; 5] = [
+		0x6745_2301,
+		0xefcd_ab89,
+		0x98ba_dcfe,
+		0x1032_5476,
+		0xc3d2_e1f0,
+	];
+
+	let mut message = data.to_vec();
+	message.push(0x80);
+	while message.len() % 64 != 56 {
+		message.push(0);
+	}
+	message.extend_from_slice(&(data.len() as u64 * 8).to_be_bytes());
+
+	for chunk in message.chunks_exact(64) {
+		let mut w = [0u32; 80];
+		for (w, word) in w.iter_mut().zip(chunk.chunks_exact(4)) {
+			*w = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+		}
+		for i in 16..80 {
+			w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+		}
+
+		let [mut a, mut b, mut c, mut d, mut e] = h;
+		for (i, w) in w.into_iter().enumerate() {
+			let (f, k) = match i {
+				0..=19 => ((b & c) | (!b & d), 0x5a82_7999),
+				20..=39 => (b ^ c ^ d, 0x6ed9_eba1),
+				40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1b_bcdc),
+				_ => (b ^ c ^ d, 0xca62_c1d6),
+			};
+			let temp = a
+				.rotate_left(5)
+				.wrapping_add(f)
+				.wrapping_add(e)
+				.wrapping_add(k)
+				.wrapping_add(w);
+			e = d;
+			d = c;
+			c = b.rotate_left(30);
+			b = a;
+			a = temp;
+		}
+		for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+			*h = h.wrapping_add(v);
+		}
+	}
+
+	let mut digest = [0; 20];
+	for (bytes, h) in digest.chunks_exact_mut(4).zip(h) {
+		bytes.copy_from_slice(&h.to_be_bytes());
+	}
+	digest
+}
+
+fn base64(data: &[u8]) -> String {
+	const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+	let mut s = String::new();
+	for chunk in data.chunks(3) {
+		let n = chunk
+			.iter()
+			.enumerate()
+			.fold(0u32, |n, (i, &byte)| n | (u32::from(byte) << (16 - 8 * i)));
+		for i in 0..4 {
+			if i <= chunk.len() {
+				s.push(char::from(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f]));
+			} else {
+				s.push('=');
+			}
+		}
+	}
+	s
+}
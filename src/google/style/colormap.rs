@@ -0,0 +1,749 @@
+use crate::google::style::Color;
+
+/// How colors are interpolated between the stops of a [`Colormap`].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+	/// Linear interpolation of the red, green and blue components.
+	Rgb,
+	/// Linear interpolation in the [CIELAB](https://en.wikipedia.org/wiki/CIELAB_color_space) color space.
+	Lab,
+	/// Linear interpolation in the [Oklab](https://bottosson.github.io/posts/oklab/) color space.
+	OkLab,
+}
+
+/// A continuous color scale, mapping numbers between `0.0` and `1.0` to colors.
+///
+/// A colormap interpolates between evenly spaced color stops. The predefined colormaps are sampled from their
+/// [matplotlib](https://matplotlib.org/stable/users/explain/colors/colormaps.html) and
+/// [ColorBrewer](https://colorbrewer2.org) definitions.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::{Color, Colormap, Interpolation};
+///
+/// let viridis = Colormap::viridis();
+/// assert!(matches!(viridis.at(0.0), Color::RGB(0x44, 0x01, 0x54)));
+/// assert!(matches!(viridis.at(1.0), Color::RGB(0xfd, 0xe7, 0x25)));
+///
+/// let turbo = Colormap::turbo();
+/// assert!(matches!(turbo.at(0.0), Color::RGB(0x30, 0x12, 0x3b)));
+/// assert!(matches!(turbo.at(1.0), Color::RGB(0x7a, 0x04, 0x03)));
+///
+/// let custom = Colormap::new([Color::White, Color::Navy]).interpolation(Interpolation::Lab);
+/// assert_eq!(custom.colors(3).len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Colormap {
+	stops: Vec<Color>,
+	interpolation: Interpolation,
+}
+
+impl Colormap {
+	/// Create a new colormap through the evenly spaced `stops`, interpolated in Oklab.
+	#[must_use]
+	pub fn new(stops: impl IntoIterator<Item = Color>) -> Self {
+		Colormap {
+			stops: stops.into_iter().collect(),
+			interpolation: Interpolation::OkLab,
+		}
+	}
+
+	fn from_table(table: &[(u8, u8, u8)]) -> Self {
+		Colormap::new(table.iter().map(|&(r, g, b)| Color::RGB(r, g, b)))
+	}
+
+	/// The perceptually uniform sequential colormap from dark blue through green to yellow.
+	#[must_use]
+	pub fn viridis() -> Self {
+		Colormap::from_table(&VIRIDIS)
+	}
+
+	/// The perceptually uniform sequential colormap from black through purple to light yellow.
+	#[must_use]
+	pub fn magma() -> Self {
+		Colormap::from_table(&MAGMA)
+	}
+
+	/// The perceptually uniform sequential colormap from dark blue through magenta to yellow.
+	#[must_use]
+	pub fn plasma() -> Self {
+		Colormap::from_table(&PLASMA)
+	}
+
+	/// The perceptually uniform sequential colormap from black through red to yellow.
+	#[must_use]
+	pub fn inferno() -> Self {
+		Colormap::from_table(&INFERNO)
+	}
+
+	/// The perceptually uniform sequential colormap from blue to yellow, designed for color vision deficiencies.
+	#[must_use]
+	pub fn cividis() -> Self {
+		Colormap::from_table(&CIVIDIS)
+	}
+
+	/// The rainbow colormap from dark blue through green and yellow to dark red.
+	#[must_use]
+	pub fn turbo() -> Self {
+		Colormap::from_table(&TURBO)
+	}
+
+	/// The diverging colormap from dark red through white to dark blue.
+	#[must_use]
+	pub fn rd_bu() -> Self {
+		Colormap::from_table(&RD_BU)
+	}
+
+	/// The diverging colormap from dark red through yellow to dark blue.
+	#[must_use]
+	pub fn spectral() -> Self {
+		Colormap::from_table(&SPECTRAL)
+	}
+
+	/// The diverging colormap from brown through white to dark green.
+	#[must_use]
+	pub fn br_bg() -> Self {
+		Colormap::from_table(&BR_BG)
+	}
+
+	/// The diverging colormap from pink through white to green.
+	#[must_use]
+	pub fn pi_yg() -> Self {
+		Colormap::from_table(&PI_YG)
+	}
+
+	/// The color space in which colors are interpolated. Defaults to [`Interpolation::OkLab`].
+	#[must_use]
+	pub fn interpolation(mut self, value: Interpolation) -> Self {
+		self.interpolation = value;
+		self
+	}
+
+	/// The same colormap in the opposite direction.
+	#[must_use]
+	pub fn reversed(mut self) -> Self {
+		self.stops.reverse();
+		self
+	}
+
+	/// The color at `t` between `0.0` and `1.0`. Values outside this range are clamped.
+	#[must_use]
+	pub fn at(&self, t: f64) -> Color {
+		let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+		match self.stops.as_slice() {
+			[] => Color::Black,
+			[color] => *color,
+			stops => {
+				let pos = t * as_f64(stops.len() - 1);
+				#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+				let i = (pos.floor() as usize).min(stops.len() - 2);
+				interpolate(stops[i], stops[i + 1], pos - as_f64(i), self.interpolation)
+			}
+		}
+	}
+
+	/// `n` evenly spaced colors from the start to the end of the colormap, e.g. for the classes of a
+	/// [`Choropleth`](crate::google::choropleth::Choropleth) or a [`Legend`](crate::google::Legend) gradient.
+	#[must_use]
+	pub fn colors(&self, n: usize) -> Vec<Color> {
+		match n {
+			0 => Vec::new(),
+			1 => vec![self.at(0.5)],
+			n => (0..n).map(|i| self.at(as_f64(i) / as_f64(n - 1))).collect(),
+		}
+	}
+}
+
+/// How values are mapped to the range between `0.0` and `1.0` of a [`Colormap`].
+///
+/// Values outside of the range between the minimum and the maximum are clamped.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Normalization {
+	/// A linear mapping from `min` to `max`.
+	Linear(f64, f64),
+	/// A logarithmic mapping from `min` to `max`, which have to be positive.
+	Log(f64, f64),
+	/// Two linear mappings, from `min` to `center` and from `center` to `max`, mapping `center` to `0.5`.
+	Diverging(f64, f64, f64),
+}
+
+impl Normalization {
+	/// Map `value` to the range between `0.0` and `1.0`.
+	#[must_use]
+	pub fn normalize(self, value: f64) -> f64 {
+		fn linear(value: f64, min: f64, max: f64) -> f64 {
+			let t = (value - min) / (max - min);
+			if t.is_nan() {
+				0.5
+			} else {
+				t.clamp(0.0, 1.0)
+			}
+		}
+
+		match self {
+			Normalization::Linear(min, max) => linear(value, min, max),
+			Normalization::Log(min, max) => {
+				if value <= 0.0 {
+					0.0
+				} else {
+					linear(value.ln(), min.ln(), max.ln())
+				}
+			}
+			Normalization::Diverging(min, center, max) => {
+				if value < center {
+					linear(value, min, center) / 2.0
+				} else {
+					0.5 + linear(value, center, max) / 2.0
+				}
+			}
+		}
+	}
+}
+
+/// A mapping from values to colors, made of a [`Normalization`] and a [`Colormap`].
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::{ColorScale, Colormap, Normalization, PolygonStyle};
+/// use mapplot::google::{GoogleMap, Legend, Polygon};
+///
+/// let scale = ColorScale::new(Colormap::rd_bu().reversed(), Normalization::Diverging(-10.0, 0.0, 30.0));
+///
+/// let html = GoogleMap::new((46.5, 8.0), 7, "<your-apikey-here>")
+///     .draw_all([(-5.0, 7.0), (0.0, 8.0), (25.0, 9.0)].map(|(temperature, lon)| {
+///         Polygon::new([(46.0, lon), (47.0, lon), (47.0, lon + 1.0), (46.0, lon + 1.0)])
+///             .style(PolygonStyle::new().fill_color(scale.color(temperature)).fill_opacity(0.8))
+///     }))
+///     .legend(Legend::new().title("°C").color_scale(&scale, [-10.0, 0.0, 10.0, 20.0, 30.0]))
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColorScale {
+	colormap: Colormap,
+	normalization: Normalization,
+}
+
+impl ColorScale {
+	/// Create a new color scale.
+	#[must_use]
+	pub fn new(colormap: Colormap, normalization: Normalization) -> Self {
+		ColorScale {
+			colormap,
+			normalization,
+		}
+	}
+
+	/// The color of `value`.
+	#[must_use]
+	pub fn color(&self, value: f64) -> Color {
+		self.colormap.at(self.normalization.normalize(value))
+	}
+
+	/// The position of `value` between `0.0` and `1.0`.
+	#[must_use]
+	pub fn normalize(&self, value: f64) -> f64 {
+		self.normalization.normalize(value)
+	}
+
+	/// The colormap of this scale.
+	#[must_use]
+	pub fn colormap(&self) -> &Colormap {
+		&self.colormap
+	}
+}
+
+/// A set of distinct colors for categorical data.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::Palette;
+/// use mapplot::google::{GoogleMap, Marker};
+///
+/// let categories = ["depot", "customer", "supplier"];
+///
+/// let html = GoogleMap::new((51.5, -0.1), 10, "<your-apikey-here>")
+///     .draw_all(categories.iter().enumerate().map(|(i, category)| {
+///         let color = Palette::Tableau10.color(i);
+///         Marker::new((51.5, -0.1 + i as f64 * 0.01)).title(format!("{category} {color:?}"))
+///     }))
+///     .to_string();
+///
+/// assert_eq!(Palette::Category10.colors().len(), 10);
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Palette {
+	/// The ten colors of D3 and matplotlib (`tab10`).
+	Category10,
+	/// The ten colors of Tableau.
+	Tableau10,
+	/// Nine bold `ColorBrewer` colors.
+	Set1,
+	/// Eight muted `ColorBrewer` colors.
+	Set2,
+	/// Twelve light `ColorBrewer` colors.
+	Set3,
+	/// Eight dark `ColorBrewer` colors.
+	Dark2,
+	/// Twelve `ColorBrewer` colors in light and dark pairs.
+	Paired,
+	/// Nine pastel `ColorBrewer` colors.
+	Pastel1,
+	/// Eight `ColorBrewer` colors with four accents.
+	Accent,
+}
+
+impl Palette {
+	fn table(self) -> &'static [(u8, u8, u8)] {
+		match self {
+			Palette::Category10 => &CATEGORY10,
+			Palette::Tableau10 => &TABLEAU10,
+			Palette::Set1 => &SET1,
+			Palette::Set2 => &SET2,
+			Palette::Set3 => &SET3,
+			Palette::Dark2 => &DARK2,
+			Palette::Paired => &PAIRED,
+			Palette::Pastel1 => &PASTEL1,
+			Palette::Accent => &ACCENT,
+		}
+	}
+
+	/// All colors of the palette.
+	#[must_use]
+	pub fn colors(self) -> Vec<Color> {
+		self.table()
+			.iter()
+			.map(|&(r, g, b)| Color::RGB(r, g, b))
+			.collect()
+	}
+
+	/// The color of the `index`th category. The colors repeat if there are more categories than colors.
+	#[must_use]
+	pub fn color(self, index: usize) -> Color {
+		let table = self.table();
+		let (r, g, b) = table[index % table.len()];
+		Color::RGB(r, g, b)
+	}
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn as_f64(n: usize) -> f64 {
+	n as f64
+}
+
+/// Interpolate between two colors, including their alpha.
+fn interpolate(a: Color, b: Color, t: f64, interpolation: Interpolation) -> Color {
+	let (from, to) = match interpolation {
+		Interpolation::Rgb => (srgb(a), srgb(b)),
+		Interpolation::Lab => (to_lab(srgb(a)), to_lab(srgb(b))),
+		Interpolation::OkLab => (to_oklab(srgb(a)), to_oklab(srgb(b))),
+	};
+	let mixed = [0, 1, 2].map(|i| from[i] + (to[i] - from[i]) * t);
+	let rgb = match interpolation {
+		Interpolation::Rgb => mixed,
+		Interpolation::Lab => from_lab(mixed),
+		Interpolation::OkLab => from_oklab(mixed),
+	};
+
+	let [red, green, blue] = rgb.map(to_u8);
+	let alpha = f64::from(a.alpha()) + (f64::from(b.alpha()) - f64::from(a.alpha())) * t;
+	match to_u8(alpha / 255.0) {
+		255 => Color::RGB(red, green, blue),
+		alpha => Color::RGBA(red, green, blue, alpha),
+	}
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_u8(v: f64) -> u8 {
+	(v * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+/// The red, green and blue components between `0.0` and `1.0`.
+fn srgb(color: Color) -> [f64; 3] {
+	let (r, g, b) = color.to_rgb();
+	[r, g, b].map(|c| f64::from(c) / 255.0)
+}
+
+// https://en.wikipedia.org/wiki/SRGB#Transfer_function_(%22gamma%22)
+fn to_linear(c: f64) -> f64 {
+	if c <= 0.04045 {
+		c / 12.92
+	} else {
+		((c + 0.055) / 1.055).powf(2.4)
+	}
+}
+
+fn from_linear(c: f64) -> f64 {
+	if c <= 0.003_130_8 {
+		c * 12.92
+	} else {
+		1.055 * c.powf(1.0 / 2.4) - 0.055
+	}
+}
+
+// https://en.wikipedia.org/wiki/CIELAB_color_space#From_CIEXYZ_to_CIELAB, D65 white point
+const WHITE: [f64; 3] = [0.950_47, 1.0, 1.088_83];
+const DELTA: f64 = 6.0 / 29.0;
+
+#[allow(clippy::many_single_char_names)]
+fn to_lab(rgb: [f64; 3]) -> [f64; 3] {
+	let [r, g, b] = rgb.map(to_linear);
+	let xyz = [
+		0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b,
+		0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b,
+		0.019_333_9 * r + 0.119_192_0 * g + 0.950_304_1 * b,
+	];
+	let [x, y, z] = [0, 1, 2].map(|i| {
+		let t = xyz[i] / WHITE[i];
+		if t > DELTA.powi(3) {
+			t.cbrt()
+		} else {
+			t / (3.0 * DELTA * DELTA) + 4.0 / 29.0
+		}
+	});
+	[116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+#[allow(clippy::many_single_char_names)]
+fn from_lab([l, a, b]: [f64; 3]) -> [f64; 3] {
+	let fy = (l + 16.0) / 116.0;
+	let f = [fy + a / 500.0, fy, fy - b / 200.0];
+	let [x, y, z] = [0, 1, 2].map(|i| {
+		let t = if f[i] > DELTA {
+			f[i].powi(3)
+		} else {
+			3.0 * DELTA * DELTA * (f[i] - 4.0 / 29.0)
+		};
+		t * WHITE[i]
+	});
+	[
+		3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+		-0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+		0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+	]
+	.map(from_linear)
+}
+
+// https://bottosson.github.io/posts/oklab/#converting-from-linear-srgb-to-oklab
+#[allow(clippy::many_single_char_names)]
+fn to_oklab(rgb: [f64; 3]) -> [f64; 3] {
+	let [r, g, b] = rgb.map(to_linear);
+	let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+	let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+	let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+	[
+		0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+		1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+		0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+	]
+}
+
+#[allow(clippy::many_single_char_names)]
+fn from_oklab([l, a, b]: [f64; 3]) -> [f64; 3] {
+	let l_ = (l + 0.396_337_777_4 * a + 0.215_803_757_3 * b).powi(3);
+	let m_ = (l - 0.105_561_345_8 * a - 0.063_854_172_8 * b).powi(3);
+	let s_ = (l - 0.089_484_177_5 * a - 1.291_485_548_0 * b).powi(3);
+	[
+		4.076_741_662_1 * l_ - 3.307_711_591_3 * m_ + 0.230_969_929_2 * s_,
+		-1.268_438_004_6 * l_ + 2.609_757_401_1 * m_ - 0.341_319_396_5 * s_,
+		-0.004_196_086_3 * l_ - 0.703_418_614_7 * m_ + 1.707_614_701_0 * s_,
+	]
+	.map(from_linear)
+}
+
+const VIRIDIS: [(u8, u8, u8); 17] = [
+	(0x44, 0x01, 0x54),
+	(0x48, 0x18, 0x6a),
+	(0x47, 0x2c, 0x7a),
+	(0x42, 0x3f, 0x85),
+	(0x3a, 0x51, 0x8b),
+	(0x32, 0x62, 0x8d),
+	(0x2c, 0x71, 0x8e),
+	(0x26, 0x81, 0x8e),
+	(0x20, 0x90, 0x8c),
+	(0x1f, 0x9f, 0x88),
+	(0x27, 0xad, 0x80),
+	(0x3d, 0xbb, 0x73),
+	(0x5c, 0xc8, 0x62),
+	(0x81, 0xd3, 0x4c),
+	(0xaa, 0xdb, 0x31),
+	(0xd5, 0xe2, 0x19),
+	(0xfd, 0xe7, 0x25),
+];
+
+const MAGMA: [(u8, u8, u8); 17] = [
+	(0x00, 0x00, 0x04),
+	(0x09, 0x07, 0x21),
+	(0x1c, 0x10, 0x46),
+	(0x35, 0x10, 0x6a),
+	(0x50, 0x12, 0x7b),
+	(0x69, 0x1c, 0x80),
+	(0x82, 0x25, 0x81),
+	(0x9b, 0x2e, 0x7f),
+	(0xb5, 0x36, 0x79),
+	(0xcf, 0x40, 0x6f),
+	(0xe5, 0x50, 0x63),
+	(0xf4, 0x69, 0x5c),
+	(0xfb, 0x87, 0x60),
+	(0xfd, 0xa5, 0x71),
+	(0xfe, 0xc2, 0x87),
+	(0xfd, 0xe0, 0xa1),
+	(0xfc, 0xfd, 0xbf),
+];
+
+const PLASMA: [(u8, u8, u8); 17] = [
+	(0x0d, 0x08, 0x87),
+	(0x30, 0x05, 0x96),
+	(0x4b, 0x02, 0xa1),
+	(0x65, 0x00, 0xa7),
+	(0x7d, 0x03, 0xa8),
+	(0x94, 0x10, 0xa1),
+	(0xa9, 0x22, 0x95),
+	(0xbb, 0x34, 0x87),
+	(0xcb, 0x46, 0x78),
+	(0xd9, 0x58, 0x6a),
+	(0xe5, 0x6b, 0x5c),
+	(0xef, 0x7f, 0x4e),
+	(0xf7, 0x94, 0x40),
+	(0xfc, 0xab, 0x33),
+	(0xfd, 0xc3, 0x27),
+	(0xf8, 0xdd, 0x24),
+	(0xf0, 0xf9, 0x21),
+];
+
+const INFERNO: [(u8, u8, u8); 17] = [
+	(0x00, 0x00, 0x04),
+	(0x0a, 0x07, 0x23),
+	(0x20, 0x0c, 0x49),
+	(0x3c, 0x09, 0x64),
+	(0x56, 0x0f, 0x6d),
+	(0x70, 0x19, 0x6e),
+	(0x89, 0x22, 0x69),
+	(0xa2, 0x2b, 0x61),
+	(0xbb, 0x36, 0x54),
+	(0xd0, 0x45, 0x44),
+	(0xe3, 0x59, 0x32),
+	(0xf0, 0x71, 0x1e),
+	(0xf9, 0x8c, 0x09),
+	(0xfb, 0xaa, 0x0f),
+	(0xf8, 0xc9, 0x32),
+	(0xf1, 0xe8, 0x65),
+	(0xfc, 0xff, 0xa4),
+];
+
+const CIVIDIS: [(u8, u8, u8); 17] = [
+	(0x00, 0x20, 0x51),
+	(0x01, 0x2b, 0x64),
+	(0x11, 0x36, 0x6c),
+	(0x26, 0x41, 0x6d),
+	(0x3c, 0x4d, 0x6d),
+	(0x50, 0x58, 0x6d),
+	(0x62, 0x64, 0x6e),
+	(0x71, 0x6f, 0x71),
+	(0x7f, 0x7b, 0x74),
+	(0x8c, 0x87, 0x77),
+	(0x99, 0x94, 0x78),
+	(0xa9, 0xa1, 0x76),
+	(0xba, 0xae, 0x70),
+	(0xce, 0xbc, 0x67),
+	(0xe2, 0xcb, 0x5b),
+	(0xf3, 0xda, 0x4e),
+	(0xfd, 0xe9, 0x45),
+];
+
+const TURBO: [(u8, u8, u8); 17] = [
+	(0x30, 0x12, 0x3b),
+	(0x40, 0x40, 0xa2),
+	(0x46, 0x6b, 0xe3),
+	(0x42, 0x94, 0xff),
+	(0x28, 0xbc, 0xeb),
+	(0x18, 0xdd, 0xc2),
+	(0x32, 0xf2, 0x98),
+	(0x6d, 0xfe, 0x62),
+	(0xa4, 0xfc, 0x3c),
+	(0xcd, 0xec, 0x34),
+	(0xee, 0xcf, 0x3a),
+	(0xfd, 0xac, 0x34),
+	(0xfb, 0x7e, 0x21),
+	(0xeb, 0x50, 0x0e),
+	(0xd0, 0x2f, 0x05),
+	(0xa9, 0x16, 0x01),
+	(0x7a, 0x04, 0x03),
+];
+
+const RD_BU: [(u8, u8, u8); 11] = [
+	(0x67, 0x00, 0x1f),
+	(0xb2, 0x18, 0x2b),
+	(0xd6, 0x60, 0x4d),
+	(0xf4, 0xa5, 0x82),
+	(0xfd, 0xdb, 0xc7),
+	(0xf7, 0xf7, 0xf7),
+	(0xd1, 0xe5, 0xf0),
+	(0x92, 0xc5, 0xde),
+	(0x43, 0x93, 0xc3),
+	(0x21, 0x66, 0xac),
+	(0x05, 0x30, 0x61),
+];
+
+const SPECTRAL: [(u8, u8, u8); 11] = [
+	(0x9e, 0x01, 0x42),
+	(0xd5, 0x3e, 0x4f),
+	(0xf4, 0x6d, 0x43),
+	(0xfd, 0xae, 0x61),
+	(0xfe, 0xe0, 0x8b),
+	(0xff, 0xff, 0xbf),
+	(0xe6, 0xf5, 0x98),
+	(0xab, 0xdd, 0xa4),
+	(0x66, 0xc2, 0xa5),
+	(0x32, 0x88, 0xbd),
+	(0x5e, 0x4f, 0xa2),
+];
+
+const BR_BG: [(u8, u8, u8); 11] = [
+	(0x54, 0x30, 0x05),
+	(0x8c, 0x51, 0x0a),
+	(0xbf, 0x81, 0x2d),
+	(0xdf, 0xc2, 0x7d),
+	(0xf6, 0xe8, 0xc3),
+	(0xf5, 0xf5, 0xf5),
+	(0xc7, 0xea, 0xe5),
+	(0x80, 0xcd, 0xc1),
+	(0x35, 0x97, 0x8f),
+	(0x01, 0x66, 0x5e),
+	(0x00, 0x3c, 0x30),
+];
+
+const PI_YG: [(u8, u8, u8); 11] = [
+	(0x8e, 0x01, 0x52),
+	(0xc5, 0x1b, 0x7d),
+	(0xde, 0x77, 0xae),
+	(0xf1, 0xb6, 0xda),
+	(0xfd, 0xe0, 0xef),
+	(0xf7, 0xf7, 0xf7),
+	(0xe6, 0xf5, 0xd0),
+	(0xb8, 0xe1, 0x86),
+	(0x7f, 0xbc, 0x41),
+	(0x4d, 0x92, 0x21),
+	(0x27, 0x64, 0x19),
+];
+
+const CATEGORY10: [(u8, u8, u8); 10] = [
+	(0x1f, 0x77, 0xb4),
+	(0xff, 0x7f, 0x0e),
+	(0x2c, 0xa0, 0x2c),
+	(0xd6, 0x27, 0x28),
+	(0x94, 0x67, 0xbd),
+	(0x8c, 0x56, 0x4b),
+	(0xe3, 0x77, 0xc2),
+	(0x7f, 0x7f, 0x7f),
+	(0xbc, 0xbd, 0x22),
+	(0x17, 0xbe, 0xcf),
+];
+
+const TABLEAU10: [(u8, u8, u8); 10] = [
+	(0x4e, 0x79, 0xa7),
+	(0xf2, 0x8e, 0x2c),
+	(0xe1, 0x57, 0x59),
+	(0x76, 0xb7, 0xb2),
+	(0x59, 0xa1, 0x4f),
+	(0xed, 0xc9, 0x49),
+	(0xaf, 0x7a, 0xa1),
+	(0xff, 0x9d, 0xa7),
+	(0x9c, 0x75, 0x5f),
+	(0xba, 0xb0, 0xab),
+];
+
+const SET1: [(u8, u8, u8); 9] = [
+	(0xe4, 0x1a, 0x1c),
+	(0x37, 0x7e, 0xb8),
+	(0x4d, 0xaf, 0x4a),
+	(0x98, 0x4e, 0xa3),
+	(0xff, 0x7f, 0x00),
+	(0xff, 0xff, 0x33),
+	(0xa6, 0x56, 0x28),
+	(0xf7, 0x81, 0xbf),
+	(0x99, 0x99, 0x99),
+];
+
+const SET2: [(u8, u8, u8); 8] = [
+	(0x66, 0xc2, 0xa5),
+	(0xfc, 0x8d, 0x62),
+	(0x8d, 0xa0, 0xcb),
+	(0xe7, 0x8a, 0xc3),
+	(0xa6, 0xd8, 0x54),
+	(0xff, 0xd9, 0x2f),
+	(0xe5, 0xc4, 0x94),
+	(0xb3, 0xb3, 0xb3),
+];
+
+const SET3: [(u8, u8, u8); 12] = [
+	(0x8d, 0xd3, 0xc7),
+	(0xff, 0xff, 0xb3),
+	(0xbe, 0xba, 0xda),
+	(0xfb, 0x80, 0x72),
+	(0x80, 0xb1, 0xd3),
+	(0xfd, 0xb4, 0x62),
+	(0xb3, 0xde, 0x69),
+	(0xfc, 0xcd, 0xe5),
+	(0xd9, 0xd9, 0xd9),
+	(0xbc, 0x80, 0xbd),
+	(0xcc, 0xeb, 0xc5),
+	(0xff, 0xed, 0x6f),
+];
+
+const DARK2: [(u8, u8, u8); 8] = [
+	(0x1b, 0x9e, 0x77),
+	(0xd9, 0x5f, 0x02),
+	(0x75, 0x70, 0xb3),
+	(0xe7, 0x29, 0x8a),
+	(0x66, 0xa6, 0x1e),
+	(0xe6, 0xab, 0x02),
+	(0xa6, 0x76, 0x1d),
+	(0x66, 0x66, 0x66),
+];
+
+const PAIRED: [(u8, u8, u8); 12] = [
+	(0xa6, 0xce, 0xe3),
+	(0x1f, 0x78, 0xb4),
+	(0xb2, 0xdf, 0x8a),
+	(0x33, 0xa0, 0x2c),
+	(0xfb, 0x9a, 0x99),
+	(0xe3, 0x1a, 0x1c),
+	(0xfd, 0xbf, 0x6f),
+	(0xff, 0x7f, 0x00),
+	(0xca, 0xb2, 0xd6),
+	(0x6a, 0x3d, 0x9a),
+	(0xff, 0xff, 0x99),
+	(0xb1, 0x59, 0x28),
+];
+
+const PASTEL1: [(u8, u8, u8); 9] = [
+	(0xfb, 0xb4, 0xae),
+	(0xb3, 0xcd, 0xe3),
+	(0xcc, 0xeb, 0xc5),
+	(0xde, 0xcb, 0xe4),
+	(0xfe, 0xd9, 0xa6),
+	(0xff, 0xff, 0xcc),
+	(0xe5, 0xd8, 0xbd),
+	(0xfd, 0xda, 0xec),
+	(0xf2, 0xf2, 0xf2),
+];
+
+const ACCENT: [(u8, u8, u8); 8] = [
+	(0x7f, 0xc9, 0x7f),
+	(0xbe, 0xae, 0xd4),
+	(0xfd, 0xc0, 0x86),
+	(0xff, 0xff, 0x99),
+	(0x38, 0x6c, 0xb0),
+	(0xf0, 0x02, 0x7f),
+	(0xbf, 0x5b, 0x17),
+	(0x66, 0x66, 0x66),
+];
@@ -0,0 +1,806 @@
+use std::fmt::{self, Debug, Formatter};
+
+use crate::google::utils::FormatterExt;
+use crate::google::JavaScript;
+
+pub use colormap::{ColorScale, Colormap, Interpolation, Normalization, Palette};
+pub use parse::ParseColorError;
+
+mod colormap;
+mod parse;
+
+/// A color, either as RGB or HSL components or as one of the basic CSS color names.
+///
+/// The saturation, lightness and alpha components are scaled to `0..=255`. Colors can also be parsed from CSS
+/// strings, including all CSS named colors.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::Color;
+///
+/// let color: Color = "rebeccapurple".parse().unwrap();
+/// assert_eq!(color.to_rgb(), (0x66, 0x33, 0x99));
+/// assert_eq!(color.to_hsl(), (270, 127, 102));
+///
+/// assert!(matches!("#f80".parse(), Ok(Color::RGB(0xff, 0x88, 0x00))));
+/// assert!(matches!("rgba(255, 0, 0, 0.5)".parse(), Ok(Color::RGBA(255, 0, 0, 128))));
+/// assert!(matches!("hsl(120deg 100% 50% / 25%)".parse(), Ok(Color::HSLA(120, 255, 128, 64))));
+/// assert!(matches!("Navy".parse(), Ok(Color::Navy)));
+///
+/// let error = "rgb(1, 2)".parse::<Color>().unwrap_err();
+/// assert_eq!(
+///     error.to_string(),
+///     r#"invalid color function: "rgb(1, 2)", expected 3 components and an optional alpha value"#
+/// );
+/// ```
+///
+/// # Transparency
+///
+/// The `strokeColor` and `fillColor` options of Google Maps shapes are always written without alpha. Instead, the
+/// alpha of [`Color::RGBA`] and [`Color::HSLA`] is moved into `strokeOpacity` and `fillOpacity`, multiplied with the
+/// opacity of the style if one is set. Everywhere else, e.g. in a [`Legend`](crate::google::Legend), the color is
+/// written as CSS including alpha.
+///
+/// ```
+/// use mapplot::google::style::{Color, PolygonStyle};
+/// use mapplot::google::{GoogleMap, Polygon};
+///
+/// let options = |style: PolygonStyle| {
+///     let html = GoogleMap::new((0.0, 0.0), 1, "<your-apikey-here>")
+///         .draw(Polygon::new([(0.0, 0.0)]).style(style))
+///         .to_string();
+///     let start = html.find("fillColor").unwrap();
+///     let end = html[start..].find(" })").unwrap();
+///     html[start..start + end].to_string()
+/// };
+/// let fill = |color: Color| options(PolygonStyle::new().fill_color(color));
+///
+/// assert_eq!(fill(Color::RGB(255, 0, 0)), r##"fillColor: "#ff0000""##);
+/// assert_eq!(fill(Color::RGBA(255, 0, 0, 51)), r##"fillColor: "#ff0000", fillOpacity: 0.2"##);
+/// assert_eq!(fill(Color::RGBA(255, 0, 0, 255)), r##"fillColor: "#ff0000""##);
+/// assert_eq!(fill(Color::HSL(120, 255, 0)), r#"fillColor: "hsl(120, 100%, 0%)""#);
+/// assert_eq!(fill(Color::HSLA(120, 255, 0, 51)), r#"fillColor: "hsl(120, 100%, 0%)", fillOpacity: 0.2"#);
+///
+/// let named = [
+///     (Color::Black, "black"),
+///     (Color::Silver, "silver"),
+///     (Color::Gray, "gray"),
+///     (Color::White, "white"),
+///     (Color::Maroon, "maroon"),
+///     (Color::Red, "red"),
+///     (Color::Purple, "purple"),
+///     (Color::Fuchsia, "fuchsia"),
+///     (Color::Green, "green"),
+///     (Color::Lime, "lime"),
+///     (Color::Olive, "olive"),
+///     (Color::Yellow, "yellow"),
+///     (Color::Navy, "navy"),
+///     (Color::Blue, "blue"),
+///     (Color::Teal, "teal"),
+///     (Color::Aqua, "aqua"),
+/// ];
+/// for (color, name) in named {
+///     assert_eq!(fill(color), format!("fillColor: {name:?}"));
+/// }
+///
+/// // an explicit opacity is multiplied with the alpha
+/// let style = PolygonStyle::new().color(Color::RGBA(0, 0, 255, 51)).fill_opacity(0.5);
+/// assert_eq!(
+///     options(style),
+///     r##"fillColor: "#0000ff", fillOpacity: 0.1, strokeColor: "#0000ff", strokeOpacity: 0.2"##
+/// );
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+	RGB(u8, u8, u8),
+	RGBA(u8, u8, u8, u8),
+	HSL(u16, u8, u8),
+	HSLA(u16, u8, u8, u8),
+	Black,
+	Silver,
+	Gray,
+	White,
+	Maroon,
+	Red,
+	Purple,
+	Fuchsia,
+	Green,
+	Lime,
+	Olive,
+	Yellow,
+	Navy,
+	Blue,
+	Teal,
+	Aqua,
+}
+
+impl Color {
+	/// The alpha component of the color, `255` if the color is opaque.
+	#[must_use]
+	pub fn alpha(self) -> u8 {
+		match self {
+			Color::RGBA(_, _, _, a) | Color::HSLA(_, _, _, a) => a,
+			_ => 255,
+		}
+	}
+
+	/// The red, green and blue components of the color, ignoring alpha.
+	#[must_use]
+	pub fn to_rgb(self) -> (u8, u8, u8) {
+		match self {
+			Color::RGB(r, g, b) | Color::RGBA(r, g, b, _) => (r, g, b),
+			Color::HSL(h, s, l) | Color::HSLA(h, s, l, _) => hsl_to_rgb(h, s, l),
+			Color::Black => (0x00, 0x00, 0x00),
+			Color::Silver => (0xc0, 0xc0, 0xc0),
+			Color::Gray => (0x80, 0x80, 0x80),
+			Color::White => (0xff, 0xff, 0xff),
+			Color::Maroon => (0x80, 0x00, 0x00),
+			Color::Red => (0xff, 0x00, 0x00),
+			Color::Purple => (0x80, 0x00, 0x80),
+			Color::Fuchsia => (0xff, 0x00, 0xff),
+			Color::Green => (0x00, 0x80, 0x00),
+			Color::Lime => (0x00, 0xff, 0x00),
+			Color::Olive => (0x80, 0x80, 0x00),
+			Color::Yellow => (0xff, 0xff, 0x00),
+			Color::Navy => (0x00, 0x00, 0x80),
+			Color::Blue => (0x00, 0x00, 0xff),
+			Color::Teal => (0x00, 0x80, 0x80),
+			Color::Aqua => (0x00, 0xff, 0xff),
+		}
+	}
+
+	/// The hue in degrees, the saturation and the lightness of the color, ignoring alpha. Like [`Color::HSL`], the
+	/// saturation and lightness are scaled to `0..=255`.
+	#[must_use]
+	pub fn to_hsl(self) -> (u16, u8, u8) {
+		match self {
+			Color::HSL(h, s, l) | Color::HSLA(h, s, l, _) => (h % 360, s, l),
+			color => {
+				let (r, g, b) = color.to_rgb();
+				rgb_to_hsl(r, g, b)
+			}
+		}
+	}
+}
+
+// https://en.wikipedia.org/wiki/HSL_and_HSV#From_RGB
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss,
+	clippy::many_single_char_names,
+	clippy::float_cmp
+)]
+fn rgb_to_hsl(r: u8, g: u8, b: u8) -> (u16, u8, u8) {
+	let (r, g, b) = (
+		f64::from(r) / 255.0,
+		f64::from(g) / 255.0,
+		f64::from(b) / 255.0,
+	);
+	let max = r.max(g).max(b);
+	let min = r.min(g).min(b);
+	let c = max - min;
+	let l = f64::midpoint(max, min);
+
+	let h = if c == 0.0 {
+		0.0
+	} else if max == r {
+		60.0 * ((g - b) / c).rem_euclid(6.0)
+	} else if max == g {
+		60.0 * ((b - r) / c + 2.0)
+	} else {
+		60.0 * ((r - g) / c + 4.0)
+	};
+	let s = if l == 0.0 || l == 1.0 {
+		0.0
+	} else {
+		c / (1.0 - (2.0 * l - 1.0).abs())
+	};
+
+	let channel = |v: f64| (v * 255.0).round().clamp(0.0, 255.0) as u8;
+	((h.round() as u16) % 360, channel(s), channel(l))
+}
+
+// https://en.wikipedia.org/wiki/HSL_and_HSV#HSL_to_RGB
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss,
+	clippy::many_single_char_names
+)]
+fn hsl_to_rgb(h: u16, s: u8, l: u8) -> (u8, u8, u8) {
+	let h = f64::from(h % 360) / 60.0;
+	let s = f64::from(s) / 255.0;
+	let l = f64::from(l) / 255.0;
+
+	let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+	let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+	let (r, g, b) = match h as u8 {
+		0 => (c, x, 0.0),
+		1 => (x, c, 0.0),
+		2 => (0.0, c, x),
+		3 => (0.0, x, c),
+		4 => (x, 0.0, c),
+		_ => (c, 0.0, x),
+	};
+	let m = l - c / 2.0;
+	let channel = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+	(channel(r), channel(g), channel(b))
+}
+
+impl Color {
+	/// The CSS representation of the color, including alpha.
+	pub(crate) fn css(self) -> String {
+		match self {
+			Color::RGB(r, g, b) => format!("#{r:02x}{g:02x}{b:02x}"),
+			Color::RGBA(r, g, b, a) => format!("rgba({r}, {g}, {b}, {})", f64::from(a) / 255.0),
+			Color::HSL(h, s, l) => format!(
+				"hsl({h}, {s}%, {l}%)",
+				s = 100.0 * f64::from(s) / 255.0,
+				l = 100.0 * f64::from(l) / 255.0
+			),
+			Color::HSLA(h, s, l, a) => format!(
+				"hsla({h}, {s}%, {l}%, {a})",
+				s = 100.0 * f64::from(s) / 255.0,
+				l = 100.0 * f64::from(l) / 255.0,
+				a = f64::from(a) / 255.0
+			),
+			named => format!("{named:?}").to_lowercase(),
+		}
+	}
+
+	/// The color without its alpha component.
+	pub(crate) fn opaque(self) -> Color {
+		match self {
+			Color::RGBA(r, g, b, _) => Color::RGB(r, g, b),
+			Color::HSLA(h, s, l, _) => Color::HSL(h, s, l),
+			color => color,
+		}
+	}
+}
+
+/// Moves the alpha component of `color` into `opacity`, as the Google Maps `strokeColor` and `fillColor` options are
+/// meant to be opaque, multiplying an explicit opacity with the alpha.
+pub(crate) fn split_alpha(
+	color: Option<Color>,
+	opacity: Option<f32>,
+) -> (Option<Color>, Option<f32>) {
+	match color {
+		Some(color) if color.alpha() < 255 => (
+			Some(color.opaque()),
+			Some(opacity.unwrap_or(1.0) * f32::from(color.alpha()) / 255.0),
+		),
+		color => (color.map(Color::opaque), opacity),
+	}
+}
+
+impl JavaScript for Color {
+	fn fmt_js(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+		self.css().fmt_js(formatter)
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StrokePosition {
+	/// The stroke is centered on the polygon's path, with half the stroke inside the polygon and half the stroke outside the polygon.
+	Center,
+	/// The stroke lies inside the polygon.
+	Inside,
+	/// The stroke lies outside the polygon.
+	Outside,
+}
+
+impl JavaScript for StrokePosition {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			StrokePosition::Center => f.write_str("google.maps.StrokePosition.CENTER"),
+			StrokePosition::Inside => f.write_str("google.maps.StrokePosition.INSIDE"),
+			StrokePosition::Outside => f.write_str("google.maps.StrokePosition.OUTSIDE"),
+		}
+	}
+}
+
+#[allow(clippy::struct_field_names)]
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Default, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolylineStyle {
+	pub(crate) stroke_color: Option<Color>,
+	pub(crate) stroke_opacity: Option<f32>,
+	pub(crate) stroke_weight: Option<usize>,
+}
+
+impl PolylineStyle {
+	/// Create a new empty (default) polyline style.
+	#[must_use]
+	pub fn new() -> Self {
+		PolylineStyle::default()
+	}
+
+	/// The stroke color.
+	#[must_use]
+	pub fn color(mut self, value: Color) -> Self {
+		self.stroke_color = Some(value);
+		self
+	}
+
+	/// The stroke opacity between 0.0 and 1.0, multiplied with the alpha of the stroke color.
+	#[must_use]
+	pub fn opacity(mut self, value: f32) -> Self {
+		self.stroke_opacity = Some(value);
+		self
+	}
+
+	/// The stroke width in pixels.
+	#[must_use]
+	pub fn width(mut self, value: usize) -> Self {
+		self.stroke_weight = Some(value);
+		self
+	}
+
+	/// The stroke color and opacity, with the alpha of the color moved into the opacity.
+	pub(crate) fn stroke(&self) -> (Option<Color>, Option<f32>) {
+		split_alpha(self.stroke_color, self.stroke_opacity)
+	}
+}
+
+impl JavaScript for PolylineStyle {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (stroke_color, stroke_opacity) = self.stroke();
+		f.write_object()
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
+			.entry_opt("strokeWeight", &self.stroke_weight)
+			.finish()
+	}
+}
+
+impl From<Color> for PolylineStyle {
+	fn from(c: Color) -> Self {
+		PolylineStyle::default().color(c)
+	}
+}
+
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Default, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonStyle {
+	pub(crate) fill_color: Option<Color>,
+	pub(crate) fill_opacity: Option<f32>,
+	pub(crate) stroke_position: Option<StrokePosition>,
+	pub(crate) stroke_color: Option<Color>,
+	pub(crate) stroke_opacity: Option<f32>,
+	pub(crate) stroke_weight: Option<usize>,
+}
+
+impl PolygonStyle {
+	/// Create a new empty (default) polygon style.
+	#[must_use]
+	pub fn new() -> Self {
+		PolygonStyle::default()
+	}
+
+	/// Set both `fill_color` and `stroke_color`.
+	#[must_use]
+	pub fn color(mut self, value: Color) -> Self {
+		self.fill_color = Some(value);
+		self.stroke_color = Some(value);
+		self
+	}
+
+	/// Set both `fill_opacity` and `stroke_opacity`.
+	#[must_use]
+	pub fn opacity(mut self, value: f32) -> Self {
+		self.fill_opacity = Some(value);
+		self.stroke_opacity = Some(value);
+		self
+	}
+
+	/// The fill color.
+	#[must_use]
+	pub fn fill_color(mut self, value: Color) -> Self {
+		self.fill_color = Some(value);
+		self
+	}
+
+	/// The fill opacity between 0.0 and 1.0, multiplied with the alpha of the fill color.
+	#[must_use]
+	pub fn fill_opacity(mut self, value: f32) -> Self {
+		self.fill_opacity = Some(value);
+		self
+	}
+
+	/// The stroke position. Defaults to [`StrokePosition::Center`]. This property is not supported on Internet Explorer 8 and earlier.
+	#[must_use]
+	pub fn stroke_position(mut self, value: StrokePosition) -> Self {
+		self.stroke_position = Some(value);
+		self
+	}
+
+	/// The stroke color.
+	#[must_use]
+	pub fn stroke_color(mut self, value: Color) -> Self {
+		self.stroke_color = Some(value);
+		self
+	}
+
+	/// The stroke opacity between 0.0 and 1.0, multiplied with the alpha of the stroke color.
+	#[must_use]
+	pub fn stroke_opacity(mut self, value: f32) -> Self {
+		self.stroke_opacity = Some(value);
+		self
+	}
+
+	/// The stroke width in pixels.
+	#[must_use]
+	pub fn stroke_width(mut self, value: usize) -> Self {
+		self.stroke_weight = Some(value);
+		self
+	}
+
+	/// The fill color and opacity, with the alpha of the color moved into the opacity.
+	pub(crate) fn fill(&self) -> (Option<Color>, Option<f32>) {
+		split_alpha(self.fill_color, self.fill_opacity)
+	}
+
+	/// The stroke color and opacity, with the alpha of the color moved into the opacity.
+	pub(crate) fn stroke(&self) -> (Option<Color>, Option<f32>) {
+		split_alpha(self.stroke_color, self.stroke_opacity)
+	}
+}
+
+impl JavaScript for PolygonStyle {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let (fill_color, fill_opacity) = self.fill();
+		let (stroke_color, stroke_opacity) = self.stroke();
+		f.write_object()
+			.entry_opt("fillColor", &fill_color)
+			.entry_opt("fillOpacity", &fill_opacity)
+			.entry_opt("strokePosition", &self.stroke_position)
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
+			.entry_opt("strokeWeight", &self.stroke_weight)
+			.finish()
+	}
+}
+
+impl From<Color> for PolygonStyle {
+	fn from(c: Color) -> Self {
+		PolygonStyle::default().color(c)
+	}
+}
+
+/// The shape of a [`Symbol`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolPath {
+	/// A circle.
+	Circle,
+	/// A backward-pointing arrow that is closed on all sides.
+	BackwardClosedArrow,
+	/// A backward-pointing arrow that is open on one side.
+	BackwardOpenArrow,
+	/// A forward-pointing arrow that is closed on all sides.
+	ForwardClosedArrow,
+	/// A forward-pointing arrow that is open on one side.
+	ForwardOpenArrow,
+	/// A custom path in [SVG path notation](https://developer.mozilla.org/en-US/docs/Web/SVG/Tutorial/Paths), e.g.
+	/// `"M 0,-1 0,1"`. The coordinates are scaled by [`Symbol::scale`].
+	Svg(String),
+}
+
+impl JavaScript for SymbolPath {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			SymbolPath::Circle => f.write_str("google.maps.SymbolPath.CIRCLE"),
+			SymbolPath::BackwardClosedArrow => {
+				f.write_str("google.maps.SymbolPath.BACKWARD_CLOSED_ARROW")
+			}
+			SymbolPath::BackwardOpenArrow => {
+				f.write_str("google.maps.SymbolPath.BACKWARD_OPEN_ARROW")
+			}
+			SymbolPath::ForwardClosedArrow => {
+				f.write_str("google.maps.SymbolPath.FORWARD_CLOSED_ARROW")
+			}
+			SymbolPath::ForwardOpenArrow => {
+				f.write_str("google.maps.SymbolPath.FORWARD_OPEN_ARROW")
+			}
+			SymbolPath::Svg(path) => path.fmt_js(f),
+		}
+	}
+}
+
+/// A vector icon, drawn along a [`Polyline`](crate::google::Polyline) by an [`IconSequence`].
+///
+/// Unlike a polyline, a symbol isn't filled by default. Set [`fill_opacity`](Symbol::fill_opacity) to fill it.
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Symbol {
+	path: SymbolPath,
+	anchor: Option<Anchor>,
+	fill_color: Option<Color>,
+	fill_opacity: Option<f32>,
+	rotation: Option<f64>,
+	scale: Option<f64>,
+	stroke_color: Option<Color>,
+	stroke_opacity: Option<f32>,
+	stroke_weight: Option<usize>,
+}
+
+impl Symbol {
+	/// Create a new symbol with the given shape.
+	#[must_use]
+	pub fn new(path: SymbolPath) -> Self {
+		Symbol {
+			path,
+			anchor: None,
+			fill_color: None,
+			fill_opacity: None,
+			rotation: None,
+			scale: None,
+			stroke_color: None,
+			stroke_opacity: None,
+			stroke_weight: None,
+		}
+	}
+
+	/// The position of the symbol relative to the polyline, in the coordinates of the symbol's path. Defaults to
+	/// `(0, 0)`.
+	#[must_use]
+	pub fn anchor(mut self, x: f64, y: f64) -> Self {
+		self.anchor = Some(Anchor { x, y });
+		self
+	}
+
+	/// Set both `fill_color` and `stroke_color`.
+	#[must_use]
+	pub fn color(mut self, value: Color) -> Self {
+		self.fill_color = Some(value);
+		self.stroke_color = Some(value);
+		self
+	}
+
+	/// The fill color. Defaults to the stroke color of the polyline.
+	#[must_use]
+	pub fn fill_color(mut self, value: Color) -> Self {
+		self.fill_color = Some(value);
+		self
+	}
+
+	/// The fill opacity between 0.0 and 1.0, multiplied with the alpha of the fill color. Defaults to `0.0`.
+	#[must_use]
+	pub fn fill_opacity(mut self, value: f32) -> Self {
+		self.fill_opacity = Some(value);
+		self
+	}
+
+	/// The angle in degrees by which to rotate the symbol, clockwise. By default, the symbol is rotated to follow the
+	/// direction of the polyline.
+	#[must_use]
+	pub fn rotation(mut self, value: f64) -> Self {
+		self.rotation = Some(value);
+		self
+	}
+
+	/// The amount by which the symbol is scaled in size. Defaults to the stroke width of the polyline.
+	#[must_use]
+	pub fn scale(mut self, value: f64) -> Self {
+		self.scale = Some(value);
+		self
+	}
+
+	/// The stroke color. Defaults to the stroke color of the polyline.
+	#[must_use]
+	pub fn stroke_color(mut self, value: Color) -> Self {
+		self.stroke_color = Some(value);
+		self
+	}
+
+	/// The stroke opacity between 0.0 and 1.0, multiplied with the alpha of the stroke color. Defaults to the stroke
+	/// opacity of the polyline, or the alpha of the stroke color.
+	#[must_use]
+	pub fn stroke_opacity(mut self, value: f32) -> Self {
+		self.stroke_opacity = Some(value);
+		self
+	}
+
+	/// The stroke width in pixels. Defaults to the scale of the symbol.
+	#[must_use]
+	pub fn stroke_width(mut self, value: usize) -> Self {
+		self.stroke_weight = Some(value);
+		self
+	}
+}
+
+impl From<SymbolPath> for Symbol {
+	fn from(path: SymbolPath) -> Self {
+		Symbol::new(path)
+	}
+}
+
+impl JavaScript for Symbol {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		// the fill opacity of a symbol defaults to 0.0, so the alpha of the fill color only applies when it's set
+		let (fill_color, fill_opacity) = match self.fill_opacity {
+			Some(_) => split_alpha(self.fill_color, self.fill_opacity),
+			None => (self.fill_color.map(Color::opaque), None),
+		};
+		let (stroke_color, stroke_opacity) = split_alpha(self.stroke_color, self.stroke_opacity);
+		f.write_object()
+			.entry("path", &self.path)
+			.entry_opt("anchor", &self.anchor)
+			.entry_opt("fillColor", &fill_color)
+			.entry_opt("fillOpacity", &fill_opacity)
+			.entry_opt("rotation", &self.rotation)
+			.entry_opt("scale", &self.scale)
+			.entry_opt("strokeColor", &stroke_color)
+			.entry_opt("strokeOpacity", &stroke_opacity)
+			.entry_opt("strokeWeight", &self.stroke_weight)
+			.finish()
+	}
+}
+
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct Anchor {
+	x: f64,
+	y: f64,
+}
+
+impl JavaScript for Anchor {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		write!(f, "new google.maps.Point({}, {})", self.x, self.y)
+	}
+}
+
+/// A distance along a polyline.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Distance {
+	/// A percentage of the length of the polyline.
+	Percent(f64),
+	/// A distance in pixels on the screen.
+	Pixels(f64),
+}
+
+impl JavaScript for Distance {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Distance::Percent(v) => write!(f, "\"{v}%\""),
+			Distance::Pixels(v) => write!(f, "\"{v}px\""),
+		}
+	}
+}
+
+/// A [`Symbol`] drawn once or repeatedly along a [`Polyline`](crate::google::Polyline).
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::{Color, Distance, IconSequence, PolylineStyle, Symbol, SymbolPath};
+/// use mapplot::google::{GoogleMap, Polyline};
+///
+/// let path = [(46.0, 7.0), (47.0, 8.0), (46.5, 9.0)];
+///
+/// let html = GoogleMap::new((46.5, 8.0), 7, "<your-apikey-here>")
+///     // a line with an arrowhead at its end
+///     .draw(Polyline::new(path).style(Color::Red).icon(IconSequence::arrow()))
+///     // a dashed line, the line itself has to be hidden
+///     .draw(
+///         Polyline::new([(45.0, 7.0), (45.5, 9.0)])
+///             .style(PolylineStyle::new().color(Color::Blue).opacity(0.0))
+///             .icon(IconSequence::dashes(20.0)),
+///     )
+///     // a custom symbol every 10% of the line
+///     .draw(
+///         Polyline::new([(47.5, 7.0), (47.5, 9.0)]).icon(
+///             IconSequence::new(Symbol::new(SymbolPath::Circle).color(Color::Green).fill_opacity(1.0))
+///                 .offset(Distance::Percent(0.0))
+///                 .repeat(Distance::Percent(10.0)),
+///         ),
+///     )
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IconSequence {
+	icon: Symbol,
+	offset: Option<Distance>,
+	repeat: Option<Distance>,
+	fixed_rotation: Option<bool>,
+}
+
+impl IconSequence {
+	/// Create a new icon sequence drawing the given symbol once at the end of the polyline.
+	#[must_use]
+	pub fn new(icon: impl Into<Symbol>) -> Self {
+		IconSequence {
+			icon: icon.into(),
+			offset: None,
+			repeat: None,
+			fixed_rotation: None,
+		}
+	}
+
+	/// A filled arrowhead at the end of the polyline, pointing in its direction.
+	#[must_use]
+	pub fn arrow() -> Self {
+		IconSequence::new(
+			Symbol::new(SymbolPath::ForwardClosedArrow)
+				.fill_opacity(1.0)
+				.scale(3.0),
+		)
+	}
+
+	/// Arrowheads repeated along the polyline every `interval` pixels, pointing in its direction.
+	#[must_use]
+	pub fn arrows(interval: f64) -> Self {
+		IconSequence::new(Symbol::new(SymbolPath::ForwardOpenArrow).scale(2.0))
+			.offset(Distance::Pixels(interval / 2.0))
+			.repeat(Distance::Pixels(interval))
+	}
+
+	/// Dashes repeated along the polyline every `interval` pixels. To draw a dashed line, hide the line itself by
+	/// setting its opacity to `0.0`.
+	#[must_use]
+	pub fn dashes(interval: f64) -> Self {
+		IconSequence::new(
+			Symbol::new(SymbolPath::Svg("M 0,-1 0,1".to_string()))
+				.stroke_opacity(1.0)
+				.scale(3.0),
+		)
+		.offset(Distance::Pixels(0.0))
+		.repeat(Distance::Pixels(interval))
+	}
+
+	/// Dots repeated along the polyline every `interval` pixels. To draw a dotted line, hide the line itself by
+	/// setting its opacity to `0.0`.
+	#[must_use]
+	pub fn dots(interval: f64) -> Self {
+		IconSequence::new(
+			Symbol::new(SymbolPath::Circle)
+				.fill_opacity(1.0)
+				.stroke_opacity(1.0)
+				.scale(1.5),
+		)
+		.offset(Distance::Pixels(0.0))
+		.repeat(Distance::Pixels(interval))
+	}
+
+	/// The distance from the start of the polyline at which the first symbol is drawn. Defaults to
+	/// `Distance::Percent(100.0)`.
+	#[must_use]
+	pub fn offset(mut self, value: Distance) -> Self {
+		self.offset = Some(value);
+		self
+	}
+
+	/// The distance between consecutive symbols. By default, the symbol is drawn only once.
+	#[must_use]
+	pub fn repeat(mut self, value: Distance) -> Self {
+		self.repeat = Some(value);
+		self
+	}
+
+	/// If set to `true`, every symbol keeps the rotation of the symbol itself. Otherwise, the symbols are rotated to
+	/// follow the direction of the polyline. Defaults to `false`.
+	#[must_use]
+	pub fn fixed_rotation(mut self, value: bool) -> Self {
+		self.fixed_rotation = Some(value);
+		self
+	}
+}
+
+impl JavaScript for IconSequence {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_object()
+			.entry("icon", &self.icon)
+			.entry_opt("offset", &self.offset)
+			.entry_opt("repeat", &self.repeat)
+			.entry_opt("fixedRotation", &self.fixed_rotation)
+			.finish()
+	}
+}
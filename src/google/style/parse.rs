@@ -0,0 +1,323 @@
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use crate::google::style::Color;
+
+/// An error that occurred while parsing a [`Color`] from a CSS string.
+#[derive(Debug, Clone)]
+pub enum ParseColorError {
+	/// A hex color with an invalid length or digit.
+	Hex(String),
+	/// An `rgb()`, `rgba()`, `hsl()` or `hsla()` function with invalid arguments.
+	Function(String),
+	/// Neither a hex color, a color function nor a CSS color name.
+	UnknownName(String),
+}
+
+impl Display for ParseColorError {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			ParseColorError::Hex(msg) => write!(f, "invalid hex color: {msg}"),
+			ParseColorError::Function(msg) => write!(f, "invalid color function: {msg}"),
+			ParseColorError::UnknownName(name) => write!(f, "unknown color name {name:?}"),
+		}
+	}
+}
+
+impl StdError for ParseColorError {}
+
+/// Parses a CSS color: `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`, `hsl()`, `hsla()`, `transparent`
+/// or one of the 148 named colors. Names and functions are case-insensitive.
+impl FromStr for Color {
+	type Err = ParseColorError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let input = s.trim().to_ascii_lowercase();
+
+		if let Some(hex) = input.strip_prefix('#') {
+			return parse_hex(hex).ok_or_else(|| {
+				ParseColorError::Hex(format!("{s:?}, expected #rgb, #rgba, #rrggbb or #rrggbbaa"))
+			});
+		}
+
+		if let Some((name, args)) = input
+			.strip_suffix(')')
+			.and_then(|rest| rest.split_once('('))
+		{
+			return parse_function(name.trim(), args)
+				.map_err(|msg| ParseColorError::Function(format!("{s:?}, {msg}")));
+		}
+
+		if input == "transparent" {
+			return Ok(Color::RGBA(0, 0, 0, 0));
+		}
+		NAMED_COLORS
+			.binary_search_by_key(&input.as_str(), |(name, _)| name)
+			.map(|i| NAMED_COLORS[i].1)
+			.map_err(|_| ParseColorError::UnknownName(s.to_string()))
+	}
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+	if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+		return None;
+	}
+	let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok().map(|d| d * 0x11);
+	let byte = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+	match hex.len() {
+		3 => Some(Color::RGB(digit(0)?, digit(1)?, digit(2)?)),
+		4 => Some(Color::RGBA(digit(0)?, digit(1)?, digit(2)?, digit(3)?)),
+		6 => Some(Color::RGB(byte(0)?, byte(2)?, byte(4)?)),
+		8 => Some(Color::RGBA(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+		_ => None,
+	}
+}
+
+/// Parses the arguments of a color function, either comma separated (`rgb(255, 0, 0, 0.5)`) or space separated with
+/// an optional alpha after a slash (`rgb(255 0 0 / 50%)`).
+fn parse_function(name: &str, args: &str) -> Result<Color, String> {
+	let (args, alpha): (Vec<&str>, Option<&str>) = if args.contains(',') {
+		let mut args: Vec<&str> = args.split(',').map(str::trim).collect();
+		let alpha = if args.len() == 4 { args.pop() } else { None };
+		(args, alpha)
+	} else {
+		let (args, alpha) = match args.split_once('/') {
+			Some((args, alpha)) => (args, Some(alpha.trim())),
+			None => (args, None),
+		};
+		(args.split_whitespace().collect(), alpha)
+	};
+	let [first, second, third] = args[..] else {
+		return Err("expected 3 components and an optional alpha value".to_string());
+	};
+	let alpha = alpha.map(parse_alpha).transpose()?;
+
+	match name {
+		"rgb" | "rgba" => {
+			let (r, g, b) = (
+				parse_channel(first)?,
+				parse_channel(second)?,
+				parse_channel(third)?,
+			);
+			Ok(alpha.map_or(Color::RGB(r, g, b), |alpha| Color::RGBA(r, g, b, alpha)))
+		}
+		"hsl" | "hsla" => {
+			let (h, s, l) = (
+				parse_hue(first)?,
+				parse_percentage(second)?,
+				parse_percentage(third)?,
+			);
+			Ok(alpha.map_or(Color::HSL(h, s, l), |alpha| Color::HSLA(h, s, l, alpha)))
+		}
+		_ => Err(format!(
+			"unknown function {name:?}, expected rgb, rgba, hsl or hsla"
+		)),
+	}
+}
+
+fn parse_number(value: &str) -> Result<f64, String> {
+	value
+		.parse::<f64>()
+		.ok()
+		.filter(|v| v.is_finite())
+		.ok_or_else(|| format!("invalid number {value:?}"))
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_byte(value: f64) -> u8 {
+	value.round().clamp(0.0, 255.0) as u8
+}
+
+/// A color channel between `0` and `255`, or a percentage.
+fn parse_channel(value: &str) -> Result<u8, String> {
+	match value.strip_suffix('%') {
+		Some(percent) => parse_number(percent).map(|p| to_byte(p / 100.0 * 255.0)),
+		None => parse_number(value).map(to_byte),
+	}
+}
+
+/// An alpha value between `0.0` and `1.0`, or a percentage.
+fn parse_alpha(value: &str) -> Result<u8, String> {
+	match value.strip_suffix('%') {
+		Some(percent) => {
+			parse_number(percent).map(|p| to_byte(p.clamp(0.0, 100.0) / 100.0 * 255.0))
+		}
+		None => parse_number(value).map(|a| to_byte(a.clamp(0.0, 1.0) * 255.0)),
+	}
+}
+
+/// A percentage, scaled to `0..=255` like the saturation and lightness of [`Color::HSL`].
+fn parse_percentage(value: &str) -> Result<u8, String> {
+	let percent = value.strip_suffix('%').unwrap_or(value);
+	parse_number(percent).map(|p| to_byte(p.clamp(0.0, 100.0) / 100.0 * 255.0))
+}
+
+/// A hue in degrees, or with a `deg`, `rad`, `grad` or `turn` unit.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_hue(value: &str) -> Result<u16, String> {
+	let degrees = if let Some(v) = value.strip_suffix("deg") {
+		parse_number(v)?
+	} else if let Some(v) = value.strip_suffix("grad") {
+		parse_number(v)? * 0.9
+	} else if let Some(v) = value.strip_suffix("rad") {
+		parse_number(v)?.to_degrees()
+	} else if let Some(v) = value.strip_suffix("turn") {
+		parse_number(v)? * 360.0
+	} else {
+		parse_number(value)?
+	};
+	Ok((degrees.rem_euclid(360.0).round() as u16) % 360)
+}
+
+/// The CSS named colors, sorted by name.
+const NAMED_COLORS: [(&str, Color); 148] = [
+	("aliceblue", Color::RGB(0xf0, 0xf8, 0xff)),
+	("antiquewhite", Color::RGB(0xfa, 0xeb, 0xd7)),
+	("aqua", Color::Aqua),
+	("aquamarine", Color::RGB(0x7f, 0xff, 0xd4)),
+	("azure", Color::RGB(0xf0, 0xff, 0xff)),
+	("beige", Color::RGB(0xf5, 0xf5, 0xdc)),
+	("bisque", Color::RGB(0xff, 0xe4, 0xc4)),
+	("black", Color::Black),
+	("blanchedalmond", Color::RGB(0xff, 0xeb, 0xcd)),
+	("blue", Color::Blue),
+	("blueviolet", Color::RGB(0x8a, 0x2b, 0xe2)),
+	("brown", Color::RGB(0xa5, 0x2a, 0x2a)),
+	("burlywood", Color::RGB(0xde, 0xb8, 0x87)),
+	("cadetblue", Color::RGB(0x5f, 0x9e, 0xa0)),
+	("chartreuse", Color::RGB(0x7f, 0xff, 0x00)),
+	("chocolate", Color::RGB(0xd2, 0x69, 0x1e)),
+	("coral", Color::RGB(0xff, 0x7f, 0x50)),
+	("cornflowerblue", Color::RGB(0x64, 0x95, 0xed)),
+	("cornsilk", Color::RGB(0xff, 0xf8, 0xdc)),
+	("crimson", Color::RGB(0xdc, 0x14, 0x3c)),
+	("cyan", Color::RGB(0x00, 0xff, 0xff)),
+	("darkblue", Color::RGB(0x00, 0x00, 0x8b)),
+	("darkcyan", Color::RGB(0x00, 0x8b, 0x8b)),
+	("darkgoldenrod", Color::RGB(0xb8, 0x86, 0x0b)),
+	("darkgray", Color::RGB(0xa9, 0xa9, 0xa9)),
+	("darkgreen", Color::RGB(0x00, 0x64, 0x00)),
+	("darkgrey", Color::RGB(0xa9, 0xa9, 0xa9)),
+	("darkkhaki", Color::RGB(0xbd, 0xb7, 0x6b)),
+	("darkmagenta", Color::RGB(0x8b, 0x00, 0x8b)),
+	("darkolivegreen", Color::RGB(0x55, 0x6b, 0x2f)),
+	("darkorange", Color::RGB(0xff, 0x8c, 0x00)),
+	("darkorchid", Color::RGB(0x99, 0x32, 0xcc)),
+	("darkred", Color::RGB(0x8b, 0x00, 0x00)),
+	("darksalmon", Color::RGB(0xe9, 0x96, 0x7a)),
+	("darkseagreen", Color::RGB(0x8f, 0xbc, 0x8f)),
+	("darkslateblue", Color::RGB(0x48, 0x3d, 0x8b)),
+	("darkslategray", Color::RGB(0x2f, 0x4f, 0x4f)),
+	("darkslategrey", Color::RGB(0x2f, 0x4f, 0x4f)),
+	("darkturquoise", Color::RGB(0x00, 0xce, 0xd1)),
+	("darkviolet", Color::RGB(0x94, 0x00, 0xd3)),
+	("deeppink", Color::RGB(0xff, 0x14, 0x93)),
+	("deepskyblue", Color::RGB(0x00, 0xbf, 0xff)),
+	("dimgray", Color::RGB(0x69, 0x69, 0x69)),
+	("dimgrey", Color::RGB(0x69, 0x69, 0x69)),
+	("dodgerblue", Color::RGB(0x1e, 0x90, 0xff)),
+	("firebrick", Color::RGB(0xb2, 0x22, 0x22)),
+	("floralwhite", Color::RGB(0xff, 0xfa, 0xf0)),
+	("forestgreen", Color::RGB(0x22, 0x8b, 0x22)),
+	("fuchsia", Color::Fuchsia),
+	("gainsboro", Color::RGB(0xdc, 0xdc, 0xdc)),
+	("ghostwhite", Color::RGB(0xf8, 0xf8, 0xff)),
+	("gold", Color::RGB(0xff, 0xd7, 0x00)),
+	("goldenrod", Color::RGB(0xda, 0xa5, 0x20)),
+	("gray", Color::Gray),
+	("green", Color::Green),
+	("greenyellow", Color::RGB(0xad, 0xff, 0x2f)),
+	("grey", Color::RGB(0x80, 0x80, 0x80)),
+	("honeydew", Color::RGB(0xf0, 0xff, 0xf0)),
+	("hotpink", Color::RGB(0xff, 0x69, 0xb4)),
+	("indianred", Color::RGB(0xcd, 0x5c, 0x5c)),
+	("indigo", Color::RGB(0x4b, 0x00, 0x82)),
+	("ivory", Color::RGB(0xff, 0xff, 0xf0)),
+	("khaki", Color::RGB(0xf0, 0xe6, 0x8c)),
+	("lavender", Color::RGB(0xe6, 0xe6, 0xfa)),
+	("lavenderblush", Color::RGB(0xff, 0xf0, 0xf5)),
+	("lawngreen", Color::RGB(0x7c, 0xfc, 0x00)),
+	("lemonchiffon", Color::RGB(0xff, 0xfa, 0xcd)),
+	("lightblue", Color::RGB(0xad, 0xd8, 0xe6)),
+	("lightcoral", Color::RGB(0xf0, 0x80, 0x80)),
+	("lightcyan", Color::RGB(0xe0, 0xff, 0xff)),
+	("lightgoldenrodyellow", Color::RGB(0xfa, 0xfa, 0xd2)),
+	("lightgray", Color::RGB(0xd3, 0xd3, 0xd3)),
+	("lightgreen", Color::RGB(0x90, 0xee, 0x90)),
+	("lightgrey", Color::RGB(0xd3, 0xd3, 0xd3)),
+	("lightpink", Color::RGB(0xff, 0xb6, 0xc1)),
+	("lightsalmon", Color::RGB(0xff, 0xa0, 0x7a)),
+	("lightseagreen", Color::RGB(0x20, 0xb2, 0xaa)),
+	("lightskyblue", Color::RGB(0x87, 0xce, 0xfa)),
+	("lightslategray", Color::RGB(0x77, 0x88, 0x99)),
+	("lightslategrey", Color::RGB(0x77, 0x88, 0x99)),
+	("lightsteelblue", Color::RGB(0xb0, 0xc4, 0xde)),
+	("lightyellow", Color::RGB(0xff, 0xff, 0xe0)),
+	("lime", Color::Lime),
+	("limegreen", Color::RGB(0x32, 0xcd, 0x32)),
+	("linen", Color::RGB(0xfa, 0xf0, 0xe6)),
+	("magenta", Color::RGB(0xff, 0x00, 0xff)),
+	("maroon", Color::Maroon),
+	("mediumaquamarine", Color::RGB(0x66, 0xcd, 0xaa)),
+	("mediumblue", Color::RGB(0x00, 0x00, 0xcd)),
+	("mediumorchid", Color::RGB(0xba, 0x55, 0xd3)),
+	("mediumpurple", Color::RGB(0x93, 0x70, 0xdb)),
+	("mediumseagreen", Color::RGB(0x3c, 0xb3, 0x71)),
+	("mediumslateblue", Color::RGB(0x7b, 0x68, 0xee)),
+	("mediumspringgreen", Color::RGB(0x00, 0xfa, 0x9a)),
+	("mediumturquoise", Color::RGB(0x48, 0xd1, 0xcc)),
+	("mediumvioletred", Color::RGB(0xc7, 0x15, 0x85)),
+	("midnightblue", Color::RGB(0x19, 0x19, 0x70)),
+	("mintcream", Color::RGB(0xf5, 0xff, 0xfa)),
+	("mistyrose", Color::RGB(0xff, 0xe4, 0xe1)),
+	("moccasin", Color::RGB(0xff, 0xe4, 0xb5)),
+	("navajowhite", Color::RGB(0xff, 0xde, 0xad)),
+	("navy", Color::Navy),
+	("oldlace", Color::RGB(0xfd, 0xf5, 0xe6)),
+	("olive", Color::Olive),
+	("olivedrab", Color::RGB(0x6b, 0x8e, 0x23)),
+	("orange", Color::RGB(0xff, 0xa5, 0x00)),
+	("orangered", Color::RGB(0xff, 0x45, 0x00)),
+	("orchid", Color::RGB(0xda, 0x70, 0xd6)),
+	("palegoldenrod", Color::RGB(0xee, 0xe8, 0xaa)),
+	("palegreen", Color::RGB(0x98, 0xfb, 0x98)),
+	("paleturquoise", Color::RGB(0xaf, 0xee, 0xee)),
+	("palevioletred", Color::RGB(0xdb, 0x70, 0x93)),
+	("papayawhip", Color::RGB(0xff, 0xef, 0xd5)),
+	("peachpuff", Color::RGB(0xff, 0xda, 0xb9)),
+	("peru", Color::RGB(0xcd, 0x85, 0x3f)),
+	("pink", Color::RGB(0xff, 0xc0, 0xcb)),
+	("plum", Color::RGB(0xdd, 0xa0, 0xdd)),
+	("powderblue", Color::RGB(0xb0, 0xe0, 0xe6)),
+	("purple", Color::Purple),
+	("rebeccapurple", Color::RGB(0x66, 0x33, 0x99)),
+	("red", Color::Red),
+	("rosybrown", Color::RGB(0xbc, 0x8f, 0x8f)),
+	("royalblue", Color::RGB(0x41, 0x69, 0xe1)),
+	("saddlebrown", Color::RGB(0x8b, 0x45, 0x13)),
+	("salmon", Color::RGB(0xfa, 0x80, 0x72)),
+	("sandybrown", Color::RGB(0xf4, 0xa4, 0x60)),
+	("seagreen", Color::RGB(0x2e, 0x8b, 0x57)),
+	("seashell", Color::RGB(0xff, 0xf5, 0xee)),
+	("sienna", Color::RGB(0xa0, 0x52, 0x2d)),
+	("silver", Color::Silver),
+	("skyblue", Color::RGB(0x87, 0xce, 0xeb)),
+	("slateblue", Color::RGB(0x6a, 0x5a, 0xcd)),
+	("slategray", Color::RGB(0x70, 0x80, 0x90)),
+	("slategrey", Color::RGB(0x70, 0x80, 0x90)),
+	("snow", Color::RGB(0xff, 0xfa, 0xfa)),
+	("springgreen", Color::RGB(0x00, 0xff, 0x7f)),
+	("steelblue", Color::RGB(0x46, 0x82, 0xb4)),
+	("tan", Color::RGB(0xd2, 0xb4, 0x8c)),
+	("teal", Color::Teal),
+	("thistle", Color::RGB(0xd8, 0xbf, 0xd8)),
+	("tomato", Color::RGB(0xff, 0x63, 0x47)),
+	("turquoise", Color::RGB(0x40, 0xe0, 0xd0)),
+	("violet", Color::RGB(0xee, 0x82, 0xee)),
+	("wheat", Color::RGB(0xf5, 0xde, 0xb3)),
+	("white", Color::White),
+	("whitesmoke", Color::RGB(0xf5, 0xf5, 0xf5)),
+	("yellow", Color::Yellow),
+	("yellowgreen", Color::RGB(0x9a, 0xcd, 0x32)),
+];
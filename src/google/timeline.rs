@@ -0,0 +1,199 @@
+use std::fmt::{self, Formatter};
+use std::time::{Duration, SystemTime};
+
+use crate::google::utils::JavaScript;
+use crate::google::{ControlPosition, MAP_IDENT};
+use crate::time::unix_millis;
+
+/// The time or time range during which a shape is shown by the [`TimeSlider`].
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct TimeRange {
+	start: SystemTime,
+	end: SystemTime,
+}
+
+impl TimeRange {
+	pub(crate) fn new(start: SystemTime, end: SystemTime) -> Self {
+		TimeRange {
+			start: start.min(end),
+			end: start.max(end),
+		}
+	}
+
+	pub(crate) fn bounds(self) -> (SystemTime, SystemTime) {
+		(self.start, self.end)
+	}
+}
+
+/// A control on the map which shows only the shapes whose time lies within the selected time.
+///
+/// Shapes get their time with e.g. [`Marker::time`](crate::google::Marker::time) or
+/// [`Polyline::time_range`](crate::google::Polyline::time_range). Shapes without a time are always shown. The slider
+/// spans from the earliest to the latest time of all shapes.
+///
+/// Only markers, polylines, polygons, rectangles and circles have a time, as well as the
+/// [`Geometry`](crate::geometry::Geometry) points, line strings and polygons wrapping them. The members of
+/// multi-geometries and geometry collections, the markers of a [`MarkerCluster`](crate::google::MarkerCluster), an
+/// [`AnimatedTrack`](crate::google::AnimatedTrack) and a [`Choropleth`](crate::google::choropleth::Choropleth) are always shown,
+/// even if they were given a time. Draw the timed shapes on their own instead, e.g. the markers of a multi-point
+/// with [`draw_all`](crate::google::GoogleMap::draw_all).
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use mapplot::google::{GoogleMap, Marker, TimeSlider};
+///
+/// let start = UNIX_EPOCH + Duration::from_secs(1_625_480_430);
+/// let markers = (0..24u32).map(|h| {
+///     Marker::new((51.5 + f64::from(h) * 0.01, -0.127)).time(start + Duration::from_secs(u64::from(h) * 3600))
+/// });
+///
+/// let html = GoogleMap::new((51.6, -0.127), 10, "<your-apikey-here>")
+///     .draw_all(markers)
+///     .time_slider(TimeSlider::new().step(Duration::from_secs(3600)).autoplay(true))
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Copy, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeSlider {
+	step: Option<Duration>,
+	range: Option<bool>,
+	autoplay: Option<bool>,
+	interval: Option<Duration>,
+	position: Option<ControlPosition>,
+}
+
+impl TimeSlider {
+	/// Create a new time slider.
+	#[must_use]
+	pub fn new() -> Self {
+		TimeSlider::default()
+	}
+
+	/// The step of the slider. With a single slider, the shapes within one step starting at the selected time are
+	/// shown. Defaults to a hundredth of the time span of all shapes.
+	#[must_use]
+	pub fn step(mut self, value: Duration) -> Self {
+		self.step = Some(value);
+		self
+	}
+
+	/// If set to `true`, the control has two sliders selecting the start and the end of a time range. Defaults to
+	/// `false`.
+	#[must_use]
+	pub fn range(mut self, value: bool) -> Self {
+		self.range = Some(value);
+		self
+	}
+
+	/// If set to `true`, the slider advances by one step every [`interval`](TimeSlider::interval) as soon as the map
+	/// is loaded. Playback can be paused and resumed on the map. Defaults to `false`.
+	#[must_use]
+	pub fn autoplay(mut self, value: bool) -> Self {
+		self.autoplay = Some(value);
+		self
+	}
+
+	/// The time between two steps during playback. Defaults to one second.
+	#[must_use]
+	pub fn interval(mut self, value: Duration) -> Self {
+		self.interval = Some(value);
+		self
+	}
+
+	/// The position of the control. Defaults to [`ControlPosition::BottomCenter`].
+	#[must_use]
+	pub fn position(mut self, value: ControlPosition) -> Self {
+		self.position = Some(value);
+		self
+	}
+
+	/// Write the control, filtering the `[start, end, shape]` entries of the JS array `timed`.
+	pub(crate) fn fmt_control(
+		&self,
+		f: &mut Formatter<'_>,
+		timed: &str,
+		(start, end): (SystemTime, SystemTime),
+	) -> fmt::Result {
+		let (start, end) = (unix_millis(start), unix_millis(end));
+
+		f.write_str("(function () {\n")?;
+		f.write_str("\t\t\tconst position = ")?;
+		self.position
+			.unwrap_or(ControlPosition::BottomCenter)
+			.fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		write!(
+			f,
+			r#"			const start = {start}, end = {end}, range = {range};
+			const step = {step} ?? Math.max((end - start) / 100, 1);
+			let playing = false, timer = null;
+
+			const control = document.createElement("div");
+			control.style.cssText = "display: flex; align-items: center; gap: 8px; margin: 10px; padding: 6px 10px; background: white; border-radius: 2px; box-shadow: rgba(0, 0, 0, 0.3) 0 1px 4px -1px; font: 13px Roboto, Arial, sans-serif";
+			const button = document.createElement("button");
+			button.style.cssText = "width: 32px; cursor: pointer";
+			const slider = () => {{
+				const input = document.createElement("input");
+				input.type = "range";
+				input.min = start;
+				input.max = end;
+				input.step = step;
+				input.value = start;
+				input.style.width = "200px";
+				return input;
+			}};
+			const from = slider(), to = slider();
+			to.value = end;
+			const label = document.createElement("span");
+			control.append(button, from, ...(range ? [to] : []), label);
+			{map}.controls[position].push(control);
+
+			const format = t => new Date(t).toISOString().replace("T", " ").slice(0, 19);
+
+			function update() {{
+				let a = Number(from.value), b = range ? Number(to.value) : a + step;
+				if (a > b) [a, b] = [b, a];
+				for (const [s, e, shape] of {timed}) __hide(shape, "time", !(range ? s <= b && e >= a : s < b && e >= a));
+				label.textContent = range ? format(a) + " – " + format(b) : format(a);
+				button.textContent = playing ? "⏸" : "▶";
+			}}
+
+			function tick() {{
+				const width = range ? Number(to.value) - Number(from.value) : 0;
+				let next = Number(from.value) + step;
+				if (next + width > end) next = start;
+				from.value = next;
+				to.value = next + width;
+				update();
+			}}
+
+			function play(value) {{
+				playing = value;
+				clearInterval(timer);
+				if (playing) timer = setInterval(tick, {interval});
+				update();
+			}}
+
+			button.addEventListener("click", () => play(!playing));
+			from.addEventListener("input", update);
+			to.addEventListener("input", update);
+			play({autoplay});
+		}})()"#,
+			map = MAP_IDENT.0,
+			range = self.range.unwrap_or(false),
+			step = self
+				.step
+				.map_or("null".to_string(), |s| (s.as_secs_f64() * 1000.0)
+					.to_string()),
+			interval = self.interval.map_or(1000.0, |i| i.as_secs_f64() * 1000.0),
+			autoplay = self.autoplay.unwrap_or(false),
+		)
+	}
+}
@@ -0,0 +1,274 @@
+use std::fmt::{self, Formatter};
+use std::time::SystemTime;
+
+use crate::google::style::{Color, PolylineStyle};
+use crate::google::utils::JavaScript;
+use crate::google::{ControlPosition, Marker, Polyline, Shape, MAP_IDENT};
+use crate::time::unix_millis;
+use crate::{BoundingBox, Location};
+
+/// A track of timestamped locations, replayed by a marker moving along the path.
+///
+/// The map shows a control to play and pause the animation, to change its speed and to jump to any point in time.
+/// The part of the track which was already traveled is drawn in a different style than the rest.
+///
+/// # Examples
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// use mapplot::google::style::Color;
+/// use mapplot::google::{AnimatedTrack, GoogleMap};
+///
+/// let start = UNIX_EPOCH + Duration::from_secs(1_625_480_430);
+/// let points = [(51.507, -0.127), (51.752, -1.258), (52.48, -1.902)]
+///     .into_iter()
+///     .enumerate()
+///     .map(|(i, p)| (start + Duration::from_secs(i as u64 * 1800), p));
+///
+/// let html = GoogleMap::new((52.0, -1.0), 8, "<your-apikey-here>")
+///     .draw(AnimatedTrack::new(points).traveled_style(Color::Red).speed(60.0))
+///     .to_string();
+///
+/// std::fs::write("map.html", html).unwrap();
+/// ```
+#[cfg_attr(feature = "serde", serde_with::skip_serializing_none)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AnimatedTrack {
+	points: Vec<(SystemTime, Location)>,
+	#[cfg_attr(feature = "serde", serde(default))]
+	style: PolylineStyle,
+	#[cfg_attr(feature = "serde", serde(default))]
+	traveled_style: PolylineStyle,
+	marker: Option<Marker>,
+	speed: Option<f64>,
+	autoplay: Option<bool>,
+	control_position: Option<ControlPosition>,
+}
+
+impl AnimatedTrack {
+	/// Create a new track. The points are sorted by time.
+	#[must_use]
+	pub fn new(points: impl IntoIterator<Item = (SystemTime, impl Into<Location>)>) -> Self {
+		let mut points: Vec<_> = points.into_iter().map(|(t, p)| (t, p.into())).collect();
+		points.sort_by_key(|(t, _)| *t);
+		AnimatedTrack {
+			points,
+			style: PolylineStyle::new().color(Color::Gray),
+			traveled_style: PolylineStyle::new().color(Color::Blue),
+			marker: None,
+			speed: None,
+			autoplay: None,
+			control_position: None,
+		}
+	}
+
+	/// The style of the part of the track which wasn't traveled yet. Defaults to a gray line.
+	#[must_use]
+	pub fn style(mut self, value: impl Into<PolylineStyle>) -> Self {
+		self.style = value.into();
+		self
+	}
+
+	/// The style of the part of the track which was already traveled. Defaults to a blue line.
+	#[must_use]
+	pub fn traveled_style(mut self, value: impl Into<PolylineStyle>) -> Self {
+		self.traveled_style = value.into();
+		self
+	}
+
+	/// The moving marker. Its position is ignored. Defaults to a plain marker.
+	#[must_use]
+	pub fn marker(mut self, value: Marker) -> Self {
+		self.marker = Some(value);
+		self
+	}
+
+	/// The initial playback speed as a multiple of real time, e.g. `60.0` replays one minute per second. The speed
+	/// can be changed on the map. By default, the whole track is replayed in one minute. A speed that isn't finite
+	/// and positive is ignored.
+	#[must_use]
+	pub fn speed(mut self, value: f64) -> Self {
+		self.speed = Some(value).filter(|s| s.is_finite() && *s > 0.0);
+		self
+	}
+
+	/// If set to `true`, the animation starts as soon as the map is loaded. Defaults to `false`.
+	#[must_use]
+	pub fn autoplay(mut self, value: bool) -> Self {
+		self.autoplay = Some(value);
+		self
+	}
+
+	/// The position of the playback control. Defaults to [`ControlPosition::BottomCenter`].
+	#[must_use]
+	pub fn control_position(mut self, value: ControlPosition) -> Self {
+		self.control_position = Some(value);
+		self
+	}
+
+	/// The timestamps in milliseconds since the Unix epoch and the locations, as two arrays.
+	fn fmt_points(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		f.write_str("\t\t\tconst times = [")?;
+		for (i, (time, _)) in self.points.iter().enumerate() {
+			if i > 0 {
+				f.write_str(", ")?;
+			}
+			write!(f, "{}", unix_millis(*time))?;
+		}
+		f.write_str("];\n")?;
+
+		f.write_str("\t\t\tconst path = ")?;
+		self.points
+			.iter()
+			.map(|(_, p)| *p)
+			.collect::<Vec<_>>()
+			.fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		Ok(())
+	}
+}
+
+impl Shape for AnimatedTrack {
+	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let empty = || Polyline::new(Vec::<Location>::new());
+
+		f.write_str("(function () {\n")?;
+
+		self.fmt_points(f)?;
+		f.write_str("\t\t\tif (times.length === 0) return;\n")?;
+
+		f.write_str("\t\t\tconst remaining = ")?;
+		empty().style(self.style).fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		f.write_str("\t\t\tconst traveled = ")?;
+		empty().style(self.traveled_style).fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		f.write_str("\t\t\tconst marker = new google.maps.Marker({ ...")?;
+		self.marker
+			.clone()
+			.unwrap_or_else(|| Marker::new(Location::new(0.0, 0.0)))
+			.fmt_options(f, Some(MAP_IDENT))?;
+		f.write_str(" });\n")?;
+
+		write!(f, "\t\t\tconst position = ")?;
+		self.control_position
+			.unwrap_or(ControlPosition::BottomCenter)
+			.fmt_js(f)?;
+		f.write_str(";\n")?;
+
+		write!(
+			f,
+			r#"			const start = times[0], end = times[times.length - 1];
+			const baseSpeed = {speed} ?? Math.max((end - start) / 60000, 1);
+			let time = start, speed = 1, playing = false, last = null;
+
+			const control = document.createElement("div");
+			control.style.cssText = "display: flex; align-items: center; gap: 8px; margin: 10px; padding: 6px 10px; background: white; border-radius: 2px; box-shadow: rgba(0, 0, 0, 0.3) 0 1px 4px -1px; font: 13px Roboto, Arial, sans-serif";
+			const button = document.createElement("button");
+			button.style.cssText = "width: 32px; cursor: pointer";
+			const speeds = document.createElement("select");
+			for (const s of [0.25, 0.5, 1, 2, 4, 8, 16]) speeds.add(new Option(s + "×", s, s === 1, s === 1));
+			const scrubber = document.createElement("input");
+			scrubber.type = "range";
+			scrubber.min = start;
+			scrubber.max = end;
+			scrubber.step = "any";
+			scrubber.style.width = "240px";
+			const label = document.createElement("span");
+			control.append(button, speeds, scrubber, label);
+			{map}.controls[position].push(control);
+
+			function update() {{
+				let i = 0;
+				while (i < times.length - 1 && times[i + 1] <= time) i++;
+				let current = path[i];
+				if (i < times.length - 1) {{
+					const t = (time - times[i]) / (times[i + 1] - times[i]);
+					current = {{
+						lat: path[i].lat + t * (path[i + 1].lat - path[i].lat),
+						lng: path[i].lng + t * (path[i + 1].lng - path[i].lng),
+					}};
+				}}
+				traveled.setPath([...path.slice(0, i + 1), current]);
+				remaining.setPath([current, ...path.slice(i + 1)]);
+				marker.setPosition(current);
+				scrubber.value = time;
+				label.textContent = new Date(time).toISOString().replace("T", " ").slice(0, 19);
+				button.textContent = playing ? "⏸" : "▶";
+			}}
+
+			function frame(now) {{
+				if (!playing) return;
+				if (last !== null) time = Math.min(time + (now - last) * baseSpeed * speed, end);
+				last = now;
+				if (time >= end) playing = false;
+				update();
+				requestAnimationFrame(frame);
+			}}
+
+			function play(value) {{
+				playing = value;
+				last = null;
+				if (playing) {{
+					if (time >= end) time = start;
+					requestAnimationFrame(frame);
+				}}
+				update();
+			}}
+
+			button.addEventListener("click", () => play(!playing));
+			speeds.addEventListener("change", () => speed = Number(speeds.value));
+			scrubber.addEventListener("input", () => {{
+				time = Number(scrubber.value);
+				update();
+			}});
+			update();
+			play({autoplay});
+"#,
+			map = MAP_IDENT.0,
+			// also checked here for deserialized tracks
+			speed = self
+				.speed
+				.filter(|s| s.is_finite() && *s > 0.0)
+				.map_or("null".to_string(), |s| s.to_string()),
+			autoplay = self.autoplay.unwrap_or(false),
+		)?;
+
+		fmt_handle(f)
+	}
+
+	#[cfg(feature = "serde")]
+	fn serde_repr(&self) -> Option<crate::google::serde_shapes::ShapeRepr<'_>> {
+		Some(crate::google::serde_shapes::ShapeRepr::AnimatedTrack(self))
+	}
+
+	fn bounds(&self) -> Option<BoundingBox> {
+		BoundingBox::around(self.points.iter().map(|(_, l)| l))
+	}
+}
+
+/// Write the end of the track's function, returning a handle which shows and hides the track like a single shape, e.g.
+/// for the layer control.
+fn fmt_handle(f: &mut Formatter<'_>) -> fmt::Result {
+	write!(
+		f,
+		r#"			let visible = true, removed = false;
+			function show() {{
+				const shown = visible && !removed;
+				[remaining, traveled, marker].forEach(s => s.setMap(shown ? {map} : null));
+				control.style.display = shown ? "" : "none";
+				if (!shown) play(false);
+			}}
+			return {{
+				getVisible: () => visible,
+				setVisible: value => (visible = value, show()),
+				setMap: map => (removed = !map, show()),
+			}};
+		}})()"#,
+		map = MAP_IDENT.0,
+	)
+}
@@ -32,8 +32,9 @@ impl<'a, 'f> JavaScriptObject<'a, 'f> {
 		self.entry_opt(key, &Some(value))
 	}
 
+	#[allow(clippy::ref_option)]
 	pub(crate) fn entry_opt(&mut self, key: &str, value: &Option<impl JavaScript>) -> &mut Self {
-		self.result = self.result.and_then(|_| {
+		self.result = self.result.and_then(|()| {
 			if let Some(value) = value {
 				if self.pending_comma {
 					self.fmt.write_str(", ")?;
@@ -52,7 +53,7 @@ impl<'a, 'f> JavaScriptObject<'a, 'f> {
 	}
 
 	pub(crate) fn finish(&mut self) -> fmt::Result {
-		self.result.and_then(|_| self.fmt.write_str(" }"))
+		self.result.and_then(|()| self.fmt.write_str(" }"))
 	}
 }
 
@@ -120,8 +121,31 @@ impl JavaScript for BoundingBox {
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct RawIdent<'a>(pub(crate) &'a str);
 
-impl<'a> JavaScript for RawIdent<'a> {
+impl JavaScript for RawIdent<'_> {
 	fn fmt_js(&self, f: &mut Formatter<'_>) -> fmt::Result {
 		Display::fmt(self.0, f)
 	}
 }
+
+////////////////////////////////////////////////////////////////////////////////////////////////////////////////////////
+
+/// Escape text for use in HTML content and quoted attribute values.
+pub(crate) fn escape_html(s: &str) -> String {
+	let mut escaped = String::with_capacity(s.len());
+	for c in s.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&#39;"),
+			c => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+/// Escape the content of a `<script>` or `<style>` element, so it can't end the element or start a comment within it.
+pub(crate) fn escape_raw_text(s: &str) -> String {
+	s.replace("</", "<\\/").replace("<!--", "<\\!--")
+}
@@ -0,0 +1,360 @@
+//! Reading of [GPX](https://www.topografix.com/gpx.asp) tracks and waypoints.
+//!
+//! Every track segment becomes one [`Polyline`] and every waypoint one [`Marker`].
+//!
+//! # Examples
+//! ```
+//! use mapplot::google::GoogleMap;
+//! use mapplot::gpx::Gpx;
+//!
+//! let gpx: Gpx = r#"
+//!     <gpx version="1.1" creator="mapplot">
+//!         <wpt lat="51.507" lon="-0.127"><name>London</name></wpt>
+//!         <trk><trkseg>
+//!             <trkpt lat="51.507" lon="-0.127"><ele>11.0</ele><time>2021-07-05T10:20:30Z</time></trkpt>
+//!             <trkpt lat="52.480" lon="-1.902"><ele>140.0</ele><time>2021-07-05T12:42:00Z</time></trkpt>
+//!         </trkseg></trk>
+//!     </gpx>
+//! "#.parse().unwrap();
+//!
+//! let html = GoogleMap::new((51.5, -1.0), 7, "<your-apikey-here>")
+//!     .draw_all(gpx.shapes())
+//!     .to_string();
+//!
+//! std::fs::write("map.html", html).unwrap();
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::SystemTime;
+use std::{fs, io};
+
+use roxmltree::{Document, Node};
+
+use crate::google::{AnimatedTrack, Marker, Polyline, Shape};
+use crate::time::parse_rfc3339;
+use crate::Location;
+
+/// The contents of a GPX file.
+#[derive(Debug, Clone, Default)]
+pub struct Gpx {
+	tracks: Vec<Track>,
+	waypoints: Vec<Waypoint>,
+}
+
+impl Gpx {
+	/// Read and parse a GPX file.
+	///
+	/// # Errors
+	/// Returns an error if the file can't be read or isn't a valid GPX document.
+	pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+		fs::read_to_string(path)?.parse()
+	}
+
+	/// All tracks in the file.
+	#[must_use]
+	pub fn tracks(&self) -> &[Track] {
+		&self.tracks
+	}
+
+	/// All waypoints in the file.
+	#[must_use]
+	pub fn waypoints(&self) -> &[Waypoint] {
+		&self.waypoints
+	}
+
+	/// One [`Polyline`] per track segment, across all tracks.
+	pub fn polylines(&self) -> impl Iterator<Item = Polyline> + '_ {
+		self.tracks.iter().flat_map(Track::polylines)
+	}
+
+	/// One [`Marker`] per waypoint.
+	pub fn markers(&self) -> impl Iterator<Item = Marker> + '_ {
+		self.waypoints.iter().map(Marker::from)
+	}
+
+	/// All track segments and waypoints, ready to be passed to [`GoogleMap::draw_all`](crate::google::GoogleMap::draw_all).
+	#[must_use]
+	pub fn shapes(&self) -> Vec<Box<dyn Shape>> {
+		self.polylines()
+			.map(|p| Box::new(p) as Box<dyn Shape>)
+			.chain(self.markers().map(|m| Box::new(m) as Box<dyn Shape>))
+			.collect()
+	}
+}
+
+impl FromStr for Gpx {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let doc = Document::parse(s)?;
+		let root = doc.root_element();
+		if root.tag_name().name() != "gpx" {
+			return Err(Error::Invalid(format!(
+				"expected a <gpx> root element, found <{}>",
+				root.tag_name().name()
+			)));
+		}
+
+		let mut gpx = Gpx::default();
+		for node in root.children().filter(Node::is_element) {
+			match node.tag_name().name() {
+				"wpt" => gpx.waypoints.push(Waypoint::parse(node)?),
+				"trk" => gpx.tracks.push(Track::parse(node)?),
+				_ => {}
+			}
+		}
+		Ok(gpx)
+	}
+}
+
+/// A track, i.e. an ordered list of segments.
+#[derive(Debug, Clone)]
+pub struct Track {
+	name: Option<String>,
+	segments: Vec<Vec<TrackPoint>>,
+}
+
+impl Track {
+	fn parse(node: Node<'_, '_>) -> Result<Self, Error> {
+		let segments = children(node, "trkseg")
+			.map(|seg| children(seg, "trkpt").map(TrackPoint::parse).collect())
+			.collect::<Result<_, _>>()?;
+
+		Ok(Track {
+			name: child_text(node, "name"),
+			segments,
+		})
+	}
+
+	/// The GPS name of the track.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
+	/// The segments of the track. A new segment is started whenever the GPS reception was lost or the receiver turned off.
+	#[must_use]
+	pub fn segments(&self) -> &[Vec<TrackPoint>] {
+		&self.segments
+	}
+
+	/// One [`Polyline`] per segment.
+	pub fn polylines(&self) -> impl Iterator<Item = Polyline> + '_ {
+		self.segments.iter().map(Polyline::new)
+	}
+
+	/// An [`AnimatedTrack`] replaying all segments. Points without a time are skipped.
+	#[must_use]
+	pub fn animated(&self) -> AnimatedTrack {
+		AnimatedTrack::new(
+			self.segments
+				.iter()
+				.flatten()
+				.filter_map(|p| Some((p.time?, p.location))),
+		)
+	}
+}
+
+/// A single point of a track segment.
+#[derive(Debug, Copy, Clone)]
+pub struct TrackPoint {
+	location: Location,
+	elevation: Option<f64>,
+	time: Option<SystemTime>,
+}
+
+impl TrackPoint {
+	fn parse(node: Node<'_, '_>) -> Result<Self, Error> {
+		Ok(TrackPoint {
+			location: parse_location(node)?,
+			elevation: parse_elevation(node)?,
+			time: parse_time(node)?,
+		})
+	}
+
+	/// The position of the point.
+	#[must_use]
+	pub fn location(&self) -> Location {
+		self.location
+	}
+
+	/// Elevation in meters.
+	#[must_use]
+	pub fn elevation(&self) -> Option<f64> {
+		self.elevation
+	}
+
+	/// The time at which the point was recorded.
+	#[must_use]
+	pub fn time(&self) -> Option<SystemTime> {
+		self.time
+	}
+}
+
+impl From<&TrackPoint> for Location {
+	fn from(p: &TrackPoint) -> Self {
+		p.location
+	}
+}
+
+/// A waypoint, i.e. a named point of interest.
+#[derive(Debug, Clone)]
+pub struct Waypoint {
+	location: Location,
+	elevation: Option<f64>,
+	time: Option<SystemTime>,
+	name: Option<String>,
+	description: Option<String>,
+}
+
+impl Waypoint {
+	fn parse(node: Node<'_, '_>) -> Result<Self, Error> {
+		Ok(Waypoint {
+			location: parse_location(node)?,
+			elevation: parse_elevation(node)?,
+			time: parse_time(node)?,
+			name: child_text(node, "name"),
+			description: child_text(node, "desc"),
+		})
+	}
+
+	/// The position of the waypoint.
+	#[must_use]
+	pub fn location(&self) -> Location {
+		self.location
+	}
+
+	/// Elevation in meters.
+	#[must_use]
+	pub fn elevation(&self) -> Option<f64> {
+		self.elevation
+	}
+
+	/// The creation time of the waypoint.
+	#[must_use]
+	pub fn time(&self) -> Option<SystemTime> {
+		self.time
+	}
+
+	/// The GPS name of the waypoint.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
+	/// A text description of the waypoint.
+	#[must_use]
+	pub fn description(&self) -> Option<&str> {
+		self.description.as_deref()
+	}
+}
+
+impl From<&Waypoint> for Location {
+	fn from(w: &Waypoint) -> Self {
+		w.location
+	}
+}
+
+impl From<&Waypoint> for Marker {
+	fn from(w: &Waypoint) -> Self {
+		let marker = Marker::new(w.location);
+		match &w.name {
+			Some(name) => marker.title(name),
+			None => marker,
+		}
+	}
+}
+
+fn children<'a, 'input: 'a>(
+	node: Node<'a, 'input>,
+	name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+	node.children()
+		.filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn child_text(node: Node<'_, '_>, name: &str) -> Option<String> {
+	children(node, name)
+		.next()
+		.and_then(|n| n.text())
+		.map(|t| t.trim().to_string())
+}
+
+fn parse_location(node: Node<'_, '_>) -> Result<Location, Error> {
+	let coord = |attr| {
+		let value = node.attribute(attr).ok_or_else(|| {
+			Error::Invalid(format!(
+				"<{}> is missing the `{attr}` attribute",
+				node.tag_name().name()
+			))
+		})?;
+		value
+			.trim()
+			.parse::<f64>()
+			.map_err(|_| Error::Invalid(format!("invalid `{attr}` value: {value:?}")))
+	};
+	Ok(Location::new(coord("lat")?, coord("lon")?))
+}
+
+fn parse_elevation(node: Node<'_, '_>) -> Result<Option<f64>, Error> {
+	child_text(node, "ele")
+		.map(|ele| {
+			ele.parse()
+				.map_err(|_| Error::Invalid(format!("invalid elevation: {ele:?}")))
+		})
+		.transpose()
+}
+
+fn parse_time(node: Node<'_, '_>) -> Result<Option<SystemTime>, Error> {
+	child_text(node, "time")
+		.map(|time| {
+			parse_rfc3339(&time).ok_or_else(|| Error::Invalid(format!("invalid time: {time:?}")))
+		})
+		.transpose()
+}
+
+/// An error that occurred while reading a GPX file.
+#[derive(Debug)]
+pub enum Error {
+	/// The file couldn't be read.
+	Io(io::Error),
+	/// The file isn't well-formed XML.
+	Xml(roxmltree::Error),
+	/// The XML document isn't valid GPX.
+	Invalid(String),
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Io(e) => write!(f, "failed to read GPX file: {e}"),
+			Error::Xml(e) => write!(f, "malformed GPX file: {e}"),
+			Error::Invalid(msg) => write!(f, "invalid GPX file: {msg}"),
+		}
+	}
+}
+
+impl StdError for Error {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Error::Io(e) => Some(e),
+			Error::Xml(e) => Some(e),
+			Error::Invalid(_) => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+impl From<roxmltree::Error> for Error {
+	fn from(e: roxmltree::Error) -> Self {
+		Error::Xml(e)
+	}
+}
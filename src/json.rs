@@ -0,0 +1,247 @@
+//! A minimal JSON reader and writer, for the GeoJSON written by the generated pages and the messages of the preview
+//! server.
+
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// The deepest nesting of arrays and objects accepted when parsing, so malicious input can't overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+	Null,
+	Bool(bool),
+	Number(f64),
+	String(String),
+	Array(Vec<Value>),
+	Object(Vec<(String, Value)>),
+}
+
+impl Value {
+	/// The value of `key` if this is an object containing it.
+	pub(crate) fn get(&self, key: &str) -> Option<&Value> {
+		match self {
+			Value::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn as_str(&self) -> Option<&str> {
+		match self {
+			Value::String(s) => Some(s),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn as_f64(&self) -> Option<f64> {
+		match self {
+			Value::Number(n) => Some(*n),
+			_ => None,
+		}
+	}
+
+	pub(crate) fn as_array(&self) -> Option<&[Value]> {
+		match self {
+			Value::Array(values) => Some(values),
+			_ => None,
+		}
+	}
+}
+
+/// A JSON string literal with the contents of `s`.
+pub(crate) fn quote(s: &str) -> String {
+	let mut quoted = String::with_capacity(s.len() + 2);
+	quoted.push('"');
+	for c in s.chars() {
+		match c {
+			'"' => quoted.push_str("\\\""),
+			'\\' => quoted.push_str("\\\\"),
+			'\n' => quoted.push_str("\\n"),
+			'\r' => quoted.push_str("\\r"),
+			'\t' => quoted.push_str("\\t"),
+			c if c.is_control() => {
+				let _ = write!(quoted, "\\u{:04x}", u32::from(c));
+			}
+			c => quoted.push(c),
+		}
+	}
+	quoted.push('"');
+	quoted
+}
+
+/// Parse a JSON document.
+pub(crate) fn parse(s: &str) -> Result<Value, String> {
+	let mut parser = Parser {
+		chars: s.chars().peekable(),
+		depth: 0,
+	};
+	let value = parser.value()?;
+	parser.skip_whitespace();
+	match parser.chars.next() {
+		None => Ok(value),
+		Some(c) => Err(format!("unexpected {c:?} after the end of the document")),
+	}
+}
+
+struct Parser<'a> {
+	chars: Peekable<Chars<'a>>,
+	/// The number of enclosing arrays and objects.
+	depth: usize,
+}
+
+impl Parser<'_> {
+	fn skip_whitespace(&mut self) {
+		while self.chars.next_if(char::is_ascii_whitespace).is_some() {}
+	}
+
+	fn expect(&mut self, expected: char) -> Result<(), String> {
+		self.skip_whitespace();
+		match self.chars.next() {
+			Some(c) if c == expected => Ok(()),
+			Some(c) => Err(format!("expected {expected:?}, found {c:?}")),
+			None => Err(format!("expected {expected:?}, found end of input")),
+		}
+	}
+
+	fn value(&mut self) -> Result<Value, String> {
+		self.skip_whitespace();
+		match self.chars.peek() {
+			Some('{') => self.nested(Parser::object),
+			Some('[') => self.nested(Parser::array),
+			Some('"') => self.string().map(Value::String),
+			Some('-' | '0'..='9') => self.number(),
+			Some('t' | 'f' | 'n') => self.literal(),
+			Some(c) => Err(format!("unexpected {c:?}")),
+			None => Err("unexpected end of input".to_string()),
+		}
+	}
+
+	/// Parse an array or object within the nesting limit.
+	fn nested(&mut self, parse: fn(&mut Self) -> Result<Value, String>) -> Result<Value, String> {
+		if self.depth == MAX_DEPTH {
+			return Err(format!(
+				"arrays and objects nested deeper than {MAX_DEPTH} levels"
+			));
+		}
+		self.depth += 1;
+		let value = parse(self)?;
+		self.depth -= 1;
+		Ok(value)
+	}
+
+	fn object(&mut self) -> Result<Value, String> {
+		self.expect('{')?;
+		let mut entries = Vec::new();
+		self.skip_whitespace();
+		if self.chars.next_if_eq(&'}').is_some() {
+			return Ok(Value::Object(entries));
+		}
+		loop {
+			self.skip_whitespace();
+			let key = self.string()?;
+			self.expect(':')?;
+			entries.push((key, self.value()?));
+			self.skip_whitespace();
+			match self.chars.next() {
+				Some(',') => {}
+				Some('}') => return Ok(Value::Object(entries)),
+				Some(c) => return Err(format!("expected ',' or '}}', found {c:?}")),
+				None => return Err("unterminated object".to_string()),
+			}
+		}
+	}
+
+	fn array(&mut self) -> Result<Value, String> {
+		self.expect('[')?;
+		let mut values = Vec::new();
+		self.skip_whitespace();
+		if self.chars.next_if_eq(&']').is_some() {
+			return Ok(Value::Array(values));
+		}
+		loop {
+			values.push(self.value()?);
+			self.skip_whitespace();
+			match self.chars.next() {
+				Some(',') => {}
+				Some(']') => return Ok(Value::Array(values)),
+				Some(c) => return Err(format!("expected ',' or ']', found {c:?}")),
+				None => return Err("unterminated array".to_string()),
+			}
+		}
+	}
+
+	fn string(&mut self) -> Result<String, String> {
+		self.expect('"')?;
+		let mut s = String::new();
+		loop {
+			match self.chars.next() {
+				Some('"') => return Ok(s),
+				Some('\\') => match self.chars.next() {
+					Some('"') => s.push('"'),
+					Some('\\') => s.push('\\'),
+					Some('/') => s.push('/'),
+					Some('b') => s.push('\u{8}'),
+					Some('f') => s.push('\u{c}'),
+					Some('n') => s.push('\n'),
+					Some('r') => s.push('\r'),
+					Some('t') => s.push('\t'),
+					Some('u') => s.push(self.unicode_escape()?),
+					Some(c) => return Err(format!("invalid escape sequence \\{c}")),
+					None => return Err("unterminated string".to_string()),
+				},
+				Some(c) => s.push(c),
+				None => return Err("unterminated string".to_string()),
+			}
+		}
+	}
+
+	/// The character of a `\uXXXX` escape, combining surrogate pairs.
+	fn unicode_escape(&mut self) -> Result<char, String> {
+		let high = self.hex4()?;
+		if !(0xd800..0xdc00).contains(&high) {
+			return char::from_u32(high).ok_or_else(|| format!("invalid escape \\u{high:04x}"));
+		}
+		if self.chars.next() != Some('\\') || self.chars.next() != Some('u') {
+			return Err(format!("unpaired surrogate \\u{high:04x}"));
+		}
+		let low = self.hex4()?;
+		char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low.wrapping_sub(0xdc00) & 0x3ff))
+			.filter(|_| (0xdc00..0xe000).contains(&low))
+			.ok_or_else(|| format!("invalid surrogate pair \\u{high:04x}\\u{low:04x}"))
+	}
+
+	fn hex4(&mut self) -> Result<u32, String> {
+		let digits: String = (0..4).filter_map(|_| self.chars.next()).collect();
+		u32::from_str_radix(&digits, 16)
+			.ok()
+			.filter(|_| digits.len() == 4)
+			.ok_or_else(|| format!("invalid escape \\u{digits}"))
+	}
+
+	fn number(&mut self) -> Result<Value, String> {
+		let mut s = String::new();
+		while let Some(c) = self
+			.chars
+			.next_if(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+		{
+			s.push(c);
+		}
+		s.parse()
+			.map(Value::Number)
+			.map_err(|_| format!("invalid number {s:?}"))
+	}
+
+	fn literal(&mut self) -> Result<Value, String> {
+		let mut s = String::new();
+		while let Some(c) = self.chars.next_if(char::is_ascii_alphabetic) {
+			s.push(c);
+		}
+		match s.as_str() {
+			"true" => Ok(Value::Bool(true)),
+			"false" => Ok(Value::Bool(false)),
+			"null" => Ok(Value::Null),
+			_ => Err(format!("unexpected {s:?}")),
+		}
+	}
+}
@@ -0,0 +1,264 @@
+//! Reading of [KML](https://developers.google.com/kml/documentation/kmlreference) placemarks.
+//!
+//! Every placemark with a geometry becomes a [`Geometry`]: points become markers titled with the name of the
+//! placemark, line strings polylines and polygons or linear rings polygons. Styles, overlays and network links are
+//! ignored.
+//!
+//! # Examples
+//! ```
+//! use mapplot::google::GoogleMap;
+//! use mapplot::kml::Kml;
+//!
+//! let kml: Kml = r#"
+//!     <kml xmlns="http://www.opengis.net/kml/2.2">
+//!         <Document>
+//!             <Placemark>
+//!                 <name>London</name>
+//!                 <Point><coordinates>-0.127,51.507,0</coordinates></Point>
+//!             </Placemark>
+//!             <Folder>
+//!                 <Placemark>
+//!                     <name>Route</name>
+//!                     <LineString><coordinates>-0.127,51.507 -1.258,51.752 -1.902,52.48</coordinates></LineString>
+//!                 </Placemark>
+//!             </Folder>
+//!         </Document>
+//!     </kml>
+//! "#.parse().unwrap();
+//!
+//! assert_eq!(kml.placemarks()[1].name(), Some("Route"));
+//!
+//! let html = GoogleMap::new((51.5, -1.0), 7, "<your-apikey-here>")
+//!     .draw_all(kml.shapes())
+//!     .to_string();
+//!
+//! std::fs::write("map.html", html).unwrap();
+//! ```
+
+use std::error::Error as StdError;
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+use std::{fs, io};
+
+use roxmltree::{Document, Node};
+
+use crate::geometry::{polygon_from_rings, Geometry};
+use crate::google::{Marker, Polyline, Shape};
+use crate::Location;
+
+/// The placemarks of a KML file.
+#[derive(Debug, Clone, Default)]
+pub struct Kml {
+	placemarks: Vec<Placemark>,
+}
+
+impl Kml {
+	/// Read and parse a KML file.
+	///
+	/// # Errors
+	/// Returns an error if the file can't be read or isn't a valid KML document.
+	pub fn read(path: impl AsRef<Path>) -> Result<Self, Error> {
+		fs::read_to_string(path)?.parse()
+	}
+
+	/// All placemarks with a geometry, in document order, including the ones in folders.
+	#[must_use]
+	pub fn placemarks(&self) -> &[Placemark] {
+		&self.placemarks
+	}
+
+	/// The geometries of all placemarks, ready to be passed to [`GoogleMap::draw_all`](crate::google::GoogleMap::draw_all).
+	#[must_use]
+	pub fn shapes(&self) -> Vec<Box<dyn Shape>> {
+		self.placemarks
+			.iter()
+			.map(|p| Box::new(p.geometry.clone()) as Box<dyn Shape>)
+			.collect()
+	}
+}
+
+impl FromStr for Kml {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let doc = Document::parse(s)?;
+		let root = doc.root_element();
+		if root.tag_name().name() != "kml" {
+			return Err(Error::Invalid(format!(
+				"expected a <kml> root element, found <{}>",
+				root.tag_name().name()
+			)));
+		}
+
+		let placemarks = root
+			.descendants()
+			.filter(|n| n.is_element() && n.tag_name().name() == "Placemark")
+			.filter_map(|n| Placemark::parse(n).transpose())
+			.collect::<Result<_, _>>()?;
+		Ok(Kml { placemarks })
+	}
+}
+
+/// A placemark, i.e. a named geometry.
+#[derive(Debug, Clone)]
+pub struct Placemark {
+	name: Option<String>,
+	description: Option<String>,
+	geometry: Geometry,
+}
+
+impl Placemark {
+	/// The placemark, `None` if it has no supported geometry.
+	fn parse(node: Node<'_, '_>) -> Result<Option<Self>, Error> {
+		let name = child_text(node, "name");
+		let Some(geometry) = node
+			.children()
+			.filter(Node::is_element)
+			.map(|n| parse_geometry(n, name.as_deref()))
+			.find_map(Result::transpose)
+			.transpose()?
+		else {
+			return Ok(None);
+		};
+
+		Ok(Some(Placemark {
+			name,
+			description: child_text(node, "description"),
+			geometry,
+		}))
+	}
+
+	/// The name of the placemark.
+	#[must_use]
+	pub fn name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
+	/// A text or HTML description of the placemark.
+	#[must_use]
+	pub fn description(&self) -> Option<&str> {
+		self.description.as_deref()
+	}
+
+	/// The geometry of the placemark.
+	#[must_use]
+	pub fn geometry(&self) -> &Geometry {
+		&self.geometry
+	}
+}
+
+impl From<&Placemark> for Geometry {
+	fn from(p: &Placemark) -> Self {
+		p.geometry.clone()
+	}
+}
+
+/// The geometry of an element, `None` if it isn't a supported geometry. Points get `name` as their title.
+fn parse_geometry(node: Node<'_, '_>, name: Option<&str>) -> Result<Option<Geometry>, Error> {
+	let geometry = match node.tag_name().name() {
+		"Point" => {
+			let position = parse_coordinates(node)?
+				.into_iter()
+				.next()
+				.ok_or_else(|| Error::Invalid("<Point> without coordinates".to_string()))?;
+			let marker = Marker::new(position);
+			Geometry::Point(match name {
+				Some(name) => marker.title(name),
+				None => marker,
+			})
+		}
+		"LineString" => Geometry::LineString(Polyline::new(parse_coordinates(node)?)),
+		"LinearRing" => Geometry::Polygon(polygon_from_rings(vec![parse_coordinates(node)?])),
+		"Polygon" => {
+			let rings = ["outerBoundaryIs", "innerBoundaryIs"]
+				.into_iter()
+				.flat_map(|boundary| children(node, boundary))
+				.flat_map(|boundary| children(boundary, "LinearRing"))
+				.map(parse_coordinates)
+				.collect::<Result<_, _>>()?;
+			Geometry::Polygon(polygon_from_rings(rings))
+		}
+		"MultiGeometry" => Geometry::GeometryCollection(
+			node.children()
+				.filter(Node::is_element)
+				.filter_map(|n| parse_geometry(n, name).transpose())
+				.collect::<Result<_, _>>()?,
+		),
+		_ => return Ok(None),
+	};
+	Ok(Some(geometry))
+}
+
+/// The `lon,lat[,alt]` tuples of the `<coordinates>` child.
+fn parse_coordinates(node: Node<'_, '_>) -> Result<Vec<Location>, Error> {
+	child_text(node, "coordinates")
+		.unwrap_or_default()
+		.split_whitespace()
+		.map(|tuple| {
+			let mut values = tuple.split(',').map(str::parse::<f64>);
+			match (values.next(), values.next()) {
+				(Some(Ok(lon)), Some(Ok(lat))) => Ok(Location::new(lat, lon)),
+				_ => Err(Error::Invalid(format!("invalid coordinates: {tuple:?}"))),
+			}
+		})
+		.collect()
+}
+
+fn children<'a, 'input: 'a>(
+	node: Node<'a, 'input>,
+	name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+	node.children()
+		.filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn child_text(node: Node<'_, '_>, name: &str) -> Option<String> {
+	children(node, name)
+		.next()
+		.and_then(|n| n.text())
+		.map(|t| t.trim().to_string())
+}
+
+/// An error that occurred while reading a KML file.
+#[derive(Debug)]
+pub enum Error {
+	/// The file couldn't be read.
+	Io(io::Error),
+	/// The file isn't well-formed XML.
+	Xml(roxmltree::Error),
+	/// The XML document isn't valid KML.
+	Invalid(String),
+}
+
+impl Display for Error {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Io(e) => write!(f, "failed to read KML file: {e}"),
+			Error::Xml(e) => write!(f, "malformed KML file: {e}"),
+			Error::Invalid(msg) => write!(f, "invalid KML file: {msg}"),
+		}
+	}
+}
+
+impl StdError for Error {
+	fn source(&self) -> Option<&(dyn StdError + 'static)> {
+		match self {
+			Error::Io(e) => Some(e),
+			Error::Xml(e) => Some(e),
+			Error::Invalid(_) => None,
+		}
+	}
+}
+
+impl From<io::Error> for Error {
+	fn from(e: io::Error) -> Self {
+		Error::Io(e)
+	}
+}
+
+impl From<roxmltree::Error> for Error {
+	fn from(e: roxmltree::Error) -> Self {
+		Error::Xml(e)
+	}
+}
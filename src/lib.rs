@@ -16,10 +16,23 @@
 	clippy::multiple_crate_versions
 )]
 
+#[cfg(test)]
+use serde_json as _;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
+pub mod csv;
+#[cfg(feature = "geo-types")]
+pub mod geo;
+pub mod geometry;
 pub mod google;
+#[cfg(feature = "gpx")]
+pub mod gpx;
+mod json;
+#[cfg(feature = "kml")]
+pub mod kml;
+pub mod svg;
+mod time;
 
 fn hijack_formatter(f: impl Fn(&mut Formatter<'_>) -> fmt::Result) -> String {
 	struct Wrapper<F>(F)
@@ -38,6 +51,7 @@ fn hijack_formatter(f: impl Fn(&mut Formatter<'_>) -> fmt::Result) -> String {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Location {
 	lat: f64,
 	lon: f64,
@@ -67,6 +81,7 @@ impl From<&(f64, f64)> for Location {
 }
 
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoundingBox {
 	p1: Location,
 	p2: Location,
@@ -77,4 +92,29 @@ impl BoundingBox {
 	pub fn new(p1: Location, p2: Location) -> Self {
 		BoundingBox { p1, p2 }
 	}
+
+	/// The smallest box containing all `locations`, with `p1` being the south-west and `p2` the north-east corner.
+	pub(crate) fn around<'a>(locations: impl IntoIterator<Item = &'a Location>) -> Option<Self> {
+		locations
+			.into_iter()
+			.map(|l| BoundingBox { p1: *l, p2: *l })
+			.reduce(BoundingBox::union)
+	}
+
+	/// The smallest box containing both boxes.
+	pub(crate) fn union(self, other: BoundingBox) -> Self {
+		let corners = [self.p1, self.p2, other.p1, other.p2];
+		let lat = corners.iter().map(|l| l.lat);
+		let lon = corners.iter().map(|l| l.lon);
+		BoundingBox {
+			p1: Location::new(
+				lat.clone().fold(f64::INFINITY, f64::min),
+				lon.clone().fold(f64::INFINITY, f64::min),
+			),
+			p2: Location::new(
+				lat.fold(f64::NEG_INFINITY, f64::max),
+				lon.fold(f64::NEG_INFINITY, f64::max),
+			),
+		}
+	}
 }
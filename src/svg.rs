@@ -0,0 +1,321 @@
+//! Static maps drawn as [SVG](https://developer.mozilla.org/en-US/docs/Web/SVG) images, without a base map.
+//!
+//! The shapes are projected with the Web Mercator projection used by Google Maps, so they have the same proportions as
+//! on a [`GoogleMap`](crate::google::GoogleMap). Anything convertible into a [`Geometry`] can be drawn, i.e. markers,
+//! polylines, polygons and rectangles, with their styles, titles and labels. Event listeners, times and IDs are ignored.
+//!
+//! # Examples
+//! ```
+//! use mapplot::google::style::{Color, PolylineStyle};
+//! use mapplot::google::{Marker, Polyline};
+//! use mapplot::svg::SvgMap;
+//!
+//! let svg = SvgMap::new(800, 600)
+//!     .title("London to Birmingham")
+//!     .draw(Polyline::new([(51.507, -0.127), (52.48, -1.902)]).style(PolylineStyle::new().color(Color::Red)))
+//!     .draw(Marker::new((51.507, -0.127)).title("London"))
+//!     .to_string();
+//!
+//! assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="800" height="600""#));
+//! assert!(svg.contains("<title>London</title>"));
+//!
+//! std::fs::write("map.svg", svg).unwrap();
+//! ```
+
+use std::f64::consts::PI;
+use std::fmt::{self, Display, Formatter};
+
+use crate::geometry::Geometry;
+use crate::google::style::Color;
+use crate::google::utils::escape_html;
+use crate::google::{Marker, Polygon, Polyline, Shape};
+use crate::{BoundingBox, Location};
+
+/// The width and height of the world at zoom level 0, in pixels.
+const TILE_SIZE: f64 = 256.0;
+/// The highest zoom level used when fitting the shapes, so a single marker isn't zoomed in infinitely.
+const MAX_FIT_ZOOM: i32 = 18;
+/// The space kept free around the shapes when fitting them, in pixels.
+const PADDING: f64 = 16.0;
+/// The latitude of the northern edge of the Web Mercator projection.
+const MAX_LAT: f64 = 85.051_128_779_806_59;
+
+/// A static map drawn as SVG.
+///
+/// By default, the map is centered and zoomed to show all shapes. Lines and outlines default to black and 3 pixels
+/// wide, areas to black at 30% opacity. Markers are drawn as red dots above all other shapes, with their label inside
+/// and their title shown when hovering them.
+///
+/// # Examples
+/// ```
+/// use mapplot::google::style::Color;
+/// use mapplot::google::Polygon;
+/// use mapplot::svg::SvgMap;
+///
+/// let svg = SvgMap::new(400, 400)
+///     .center((0.0, 0.0), 0)
+///     .background_color(Color::White)
+///     .draw(Polygon::new([(10.0, -10.0), (10.0, 10.0), (-10.0, 10.0), (-10.0, -10.0)]))
+///     .to_string();
+///
+/// // at zoom level 0 the world is 256 pixels wide, centered in the image
+/// assert!(svg.contains(r#"<path d="M192.9,192.9 L207.1,192.9 L207.1,207.1 L192.9,207.1 Z""#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SvgMap {
+	width: u32,
+	height: u32,
+	title: Option<String>,
+	view: Option<(Location, u8)>,
+	background_color: Option<Color>,
+	shapes: Vec<Geometry>,
+}
+
+impl SvgMap {
+	/// Create a new map with the given size in pixels.
+	#[must_use]
+	pub fn new(width: u32, height: u32) -> Self {
+		SvgMap {
+			width,
+			height,
+			title: None,
+			view: None,
+			background_color: None,
+			shapes: Vec::new(),
+		}
+	}
+
+	/// Set the title of the image.
+	pub fn title(&mut self, value: impl AsRef<str>) -> &mut Self {
+		self.title = Some(value.as_ref().to_string());
+		self
+	}
+
+	/// Center the map at `center` with the given zoom level, as on a Google map, instead of fitting the shapes.
+	pub fn center(&mut self, center: impl Into<Location>, zoom: u8) -> &mut Self {
+		self.view = Some((center.into(), zoom));
+		self
+	}
+
+	/// The color of the background. Transparent by default.
+	pub fn background_color(&mut self, value: Color) -> &mut Self {
+		self.background_color = Some(value);
+		self
+	}
+
+	/// Draw a shape on the map.
+	pub fn draw(&mut self, shape: impl Into<Geometry>) -> &mut Self {
+		self.shapes.push(shape.into());
+		self
+	}
+
+	/// Draw multiple shapes at once.
+	pub fn draw_all(&mut self, shapes: impl IntoIterator<Item = impl Into<Geometry>>) -> &mut Self {
+		self.shapes.extend(shapes.into_iter().map(Into::into));
+		self
+	}
+
+	/// The projection showing the given view, or all shapes.
+	fn projection(&self) -> Projection {
+		let (width, height) = (f64::from(self.width), f64::from(self.height));
+		let ((x, y), scale) = match self.view {
+			Some((center, zoom)) => (mercator(center), TILE_SIZE * 2f64.powi(zoom.into())),
+			None => match self
+				.shapes
+				.iter()
+				.filter_map(Shape::bounds)
+				.reduce(BoundingBox::union)
+			{
+				Some(bounds) => {
+					// `p1` is the south-west corner, the y axis points south
+					let (x1, y1) = mercator(bounds.p1);
+					let (x2, y2) = mercator(bounds.p2);
+					let fit = |size: f64, extent: f64| (size - 2.0 * PADDING).max(1.0) / extent;
+					let scale = fit(width, x2 - x1)
+						.min(fit(height, y1 - y2))
+						.min(TILE_SIZE * 2f64.powi(MAX_FIT_ZOOM));
+					((f64::midpoint(x1, x2), f64::midpoint(y1, y2)), scale)
+				}
+				None => ((0.5, 0.5), width.min(height)),
+			},
+		};
+		Projection {
+			scale,
+			x0: x * scale - width / 2.0,
+			y0: y * scale - height / 2.0,
+		}
+	}
+}
+
+impl Display for SvgMap {
+	fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+		let projection = self.projection();
+		writeln!(
+			f,
+			r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+			w = self.width,
+			h = self.height,
+		)?;
+		if let Some(title) = &self.title {
+			writeln!(f, "\t<title>{}</title>", escape_html(title))?;
+		}
+		if let Some(color) = self.background_color {
+			f.write_str("\t<rect width=\"100%\" height=\"100%\"")?;
+			fmt_paint(f, "fill", color, None)?;
+			f.write_str("/>\n")?;
+		}
+		for shape in &self.shapes {
+			fmt_geometry(f, shape, &projection, false)?;
+		}
+		for shape in &self.shapes {
+			fmt_geometry(f, shape, &projection, true)?;
+		}
+		f.write_str("</svg>\n")
+	}
+}
+
+/// Web Mercator coordinates scaled to pixels and shifted into the image.
+struct Projection {
+	scale: f64,
+	x0: f64,
+	y0: f64,
+}
+
+impl Projection {
+	fn project(&self, location: Location) -> (f64, f64) {
+		let (x, y) = mercator(location);
+		(x * self.scale - self.x0, y * self.scale - self.y0)
+	}
+}
+
+/// The Web Mercator coordinates of a location, from `(0, 0)` in the north-west to `(1, 1)` in the south-east corner of
+/// the world.
+fn mercator(location: Location) -> (f64, f64) {
+	let lat = location.lat.clamp(-MAX_LAT, MAX_LAT).to_radians();
+	(
+		(location.lon + 180.0) / 360.0,
+		(1.0 - lat.tan().asinh() / PI) / 2.0,
+	)
+}
+
+/// Write either the markers or the other shapes of a geometry.
+fn fmt_geometry(
+	f: &mut Formatter<'_>,
+	geometry: &Geometry,
+	projection: &Projection,
+	markers: bool,
+) -> fmt::Result {
+	match geometry {
+		Geometry::Point(m) if markers => fmt_marker(f, m, projection),
+		Geometry::MultiPoint(m) if markers => {
+			m.iter().try_for_each(|m| fmt_marker(f, m, projection))
+		}
+		Geometry::LineString(p) if !markers => fmt_polyline(f, p, projection),
+		Geometry::MultiLineString(p) if !markers => {
+			p.iter().try_for_each(|p| fmt_polyline(f, p, projection))
+		}
+		Geometry::Polygon(p) if !markers => fmt_polygon(f, p, projection),
+		Geometry::MultiPolygon(p) if !markers => {
+			p.iter().try_for_each(|p| fmt_polygon(f, p, projection))
+		}
+		Geometry::GeometryCollection(g) => g
+			.iter()
+			.try_for_each(|g| fmt_geometry(f, g, projection, markers)),
+		_ => Ok(()),
+	}
+}
+
+fn fmt_marker(f: &mut Formatter<'_>, marker: &Marker, projection: &Projection) -> fmt::Result {
+	let (x, y) = projection.project(marker.position);
+	write!(f, "\t<g transform=\"translate({x:.1} {y:.1})\"")?;
+	if let Some(opacity) = marker.opacity {
+		write!(f, " opacity=\"{opacity}\"")?;
+	}
+	f.write_str(">")?;
+	if let Some(title) = &marker.title {
+		write!(f, "<title>{}</title>", escape_html(title))?;
+	}
+	f.write_str(r##"<circle r="7" fill="#ea4335" stroke="#ffffff" stroke-width="2"/>"##)?;
+	if let Some(label) = &marker.label {
+		write!(
+			f,
+			r##"<text text-anchor="middle" dominant-baseline="central" fill="#ffffff" font-family="sans-serif" font-size="10">{}</text>"##,
+			escape_html(label)
+		)?;
+	}
+	f.write_str("</g>\n")
+}
+
+fn fmt_polyline(
+	f: &mut Formatter<'_>,
+	polyline: &Polyline,
+	projection: &Projection,
+) -> fmt::Result {
+	if polyline.common.visible == Some(false) || polyline.path.is_empty() {
+		return Ok(());
+	}
+	f.write_str("\t<polyline points=\"")?;
+	for (i, location) in polyline.path.iter().enumerate() {
+		let (x, y) = projection.project(*location);
+		let separator = if i > 0 { " " } else { "" };
+		write!(f, "{separator}{x:.1},{y:.1}")?;
+	}
+	f.write_str("\" fill=\"none\"")?;
+	let (color, opacity) = polyline.style.stroke();
+	fmt_stroke(f, color, opacity, polyline.style.stroke_weight)?;
+	f.write_str(" stroke-linecap=\"round\" stroke-linejoin=\"round\"/>\n")
+}
+
+fn fmt_polygon(f: &mut Formatter<'_>, polygon: &Polygon, projection: &Projection) -> fmt::Result {
+	if polygon.common.visible == Some(false) || polygon.paths.iter().all(Vec::is_empty) {
+		return Ok(());
+	}
+	f.write_str("\t<path d=\"")?;
+	for (i, path) in polygon.paths.iter().filter(|p| !p.is_empty()).enumerate() {
+		if i > 0 {
+			f.write_str(" ")?;
+		}
+		for (j, location) in path.iter().enumerate() {
+			let (x, y) = projection.project(*location);
+			let command = if j > 0 { " L" } else { "M" };
+			write!(f, "{command}{x:.1},{y:.1}")?;
+		}
+		f.write_str(" Z")?;
+	}
+	f.write_str("\" fill-rule=\"evenodd\"")?;
+	let (color, opacity) = polygon.style.fill();
+	fmt_paint(
+		f,
+		"fill",
+		color.unwrap_or(Color::Black),
+		opacity.or(Some(0.3)),
+	)?;
+	let (color, opacity) = polygon.style.stroke();
+	fmt_stroke(f, color, opacity, polygon.style.stroke_weight)?;
+	f.write_str(" stroke-linejoin=\"round\"/>\n")
+}
+
+fn fmt_stroke(
+	f: &mut Formatter<'_>,
+	color: Option<Color>,
+	opacity: Option<f32>,
+	width: Option<usize>,
+) -> fmt::Result {
+	fmt_paint(f, "stroke", color.unwrap_or(Color::Black), opacity)?;
+	write!(f, " stroke-width=\"{}\"", width.unwrap_or(3))
+}
+
+/// Write a `fill` or `stroke` attribute with its opacity, moving the alpha of the color into the opacity.
+fn fmt_paint(
+	f: &mut Formatter<'_>,
+	attribute: &str,
+	color: Color,
+	opacity: Option<f32>,
+) -> fmt::Result {
+	let alpha = f32::from(color.alpha()) / 255.0;
+	write!(f, " {attribute}=\"{}\"", color.opaque().css())?;
+	match opacity.map_or(alpha, |o| o * alpha) {
+		opacity if opacity < 1.0 => write!(f, " {attribute}-opacity=\"{opacity}\""),
+		_ => Ok(()),
+	}
+}
@@ -0,0 +1,90 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Parse an RFC 3339 timestamp, e.g. `2021-07-05T10:20:30.5+02:00`.
+///
+/// A missing offset is interpreted as UTC.
+pub(crate) fn parse_rfc3339(s: &str) -> Option<SystemTime> {
+	let s = s.trim();
+	let (date, time) = s.split_once(['T', 't', ' '])?;
+
+	let mut date = date.splitn(3, '-');
+	let year: i64 = date.next()?.parse().ok()?;
+	let month: u32 = date.next()?.parse().ok()?;
+	let day: u32 = date.next()?.parse().ok()?;
+	if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+		return None;
+	}
+
+	let (time, offset) = if let Some(time) = time.strip_suffix(['Z', 'z']) {
+		(time, 0)
+	} else if let Some(idx) = time.rfind(['+', '-']) {
+		let (time, offset) = time.split_at(idx);
+		let sign = if offset.starts_with('-') { -1 } else { 1 };
+		// `HH:MM`, or `HHMM` as in ISO 8601
+		let offset = &offset[1..];
+		let (h, m) = offset
+			.split_once(':')
+			.or_else(|| offset.split_at_checked(2).filter(|(_, m)| m.len() == 2))?;
+		if h.len() != 2 || m.len() != 2 {
+			return None;
+		}
+		let h: i64 = h.parse().ok().filter(|h| (0..24).contains(h))?;
+		let m: i64 = m.parse().ok().filter(|m| (0..60).contains(m))?;
+		(time, sign * (h * 3600 + m * 60))
+	} else {
+		(time, 0)
+	};
+
+	let mut time = time.splitn(3, ':');
+	let hour: i64 = time.next()?.parse().ok()?;
+	let minute: i64 = time.next()?.parse().ok()?;
+	let second: f64 = time.next().unwrap_or("0").parse().ok()?;
+	if hour > 23 || minute > 59 || !(0.0..61.0).contains(&second) {
+		return None;
+	}
+
+	let days = days_from_civil(year, month, day)?;
+	let secs = days
+		.checked_mul(86400)?
+		.checked_add(hour * 3600 + minute * 60 - offset)?;
+	let nanos = Duration::from_secs_f64(second);
+
+	if secs >= 0 {
+		(UNIX_EPOCH + nanos).checked_add(Duration::from_secs(secs.unsigned_abs()))
+	} else {
+		(UNIX_EPOCH + nanos).checked_sub(Duration::from_secs(secs.unsigned_abs()))
+	}
+}
+
+/// Number of milliseconds since the Unix epoch, negative for earlier times.
+pub(crate) fn unix_millis(time: SystemTime) -> f64 {
+	match time.duration_since(UNIX_EPOCH) {
+		Ok(d) => d.as_secs_f64() * 1000.0,
+		Err(e) => -e.duration().as_secs_f64() * 1000.0,
+	}
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+	match month {
+		2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+		2 => 28,
+		4 | 6 | 9 | 11 => 30,
+		_ => 31,
+	}
+}
+
+/// The number of days since the Unix epoch, `None` if it overflows.
+// http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: i64, month: u32, day: u32) -> Option<i64> {
+	let y = if month <= 2 {
+		year.checked_sub(1)?
+	} else {
+		year
+	};
+	let era = y.div_euclid(400);
+	let yoe = y - era * 400;
+	let m = i64::from(month);
+	let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(day) - 1;
+	let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+	era.checked_mul(146_097)?.checked_add(doe - 719_468)
+}